@@ -1,16 +1,83 @@
 use model::TokenAmount;
-use near_sdk::require;
+use near_sdk::env;
 
 use crate::{common::Timestamp, jar::model::Jar, product::model::Product};
 
+pub(crate) fn check_not_locked(jar: &Jar) -> Result<(), String> {
+    if jar.is_pending_withdraw {
+        return Err("Another operation on this Jar is in progress".to_string());
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_not_frozen(jar: &Jar) -> Result<(), String> {
+    if jar.is_frozen() {
+        return Err(format!("Jar with id: {} is frozen", jar.id));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_not_unbonding(jar: &Jar) -> Result<(), String> {
+    if jar.is_unbonding() {
+        return Err(format!("Jar with id: {} has a pending withdrawal request", jar.id));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_sufficient_balance(jar: &Jar, amount: TokenAmount) -> Result<(), String> {
+    if jar.principal < amount {
+        return Err("Insufficient balance".to_string());
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_is_liquidable(jar: &Jar, product: &Product, now: Timestamp) -> Result<(), String> {
+    if !jar.is_liquidable(product, now) {
+        return Err("The jar is not mature yet".to_string());
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_is_restakable(jar: &Jar, product: &Product, now: Timestamp) -> Result<(), String> {
+    check_is_liquidable(jar, product, now)?;
+
+    if jar.is_empty() {
+        return Err("The jar is empty, nothing to restake".to_string());
+    }
+
+    Ok(())
+}
+
+/// Unwraps a result of a `check_*` function panicking with the reason of a failure.
+pub(crate) fn require_ok<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|message| env::panic_str(&message))
+}
+
 pub(crate) fn assert_not_locked(jar: &Jar) {
-    require!(!jar.is_pending_withdraw, "Another operation on this Jar is in progress");
+    require_ok(check_not_locked(jar));
+}
+
+pub(crate) fn assert_not_frozen(jar: &Jar) {
+    require_ok(check_not_frozen(jar));
+}
+
+pub(crate) fn assert_not_unbonding(jar: &Jar) {
+    require_ok(check_not_unbonding(jar));
 }
 
 pub(crate) fn assert_sufficient_balance(jar: &Jar, amount: TokenAmount) {
-    require!(jar.principal >= amount, "Insufficient balance");
+    require_ok(check_sufficient_balance(jar, amount));
 }
 
 pub(crate) fn assert_is_liquidable(jar: &Jar, product: &Product, now: Timestamp) {
-    require!(jar.is_liquidable(product, now), "The jar is not mature yet");
+    require_ok(check_is_liquidable(jar, product, now));
+}
+
+pub(crate) fn assert_is_restakable(jar: &Jar, product: &Product, now: Timestamp) {
+    require_ok(check_is_restakable(jar, product, now));
 }
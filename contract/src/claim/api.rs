@@ -1,13 +1,29 @@
 use std::cmp;
 
-use model::{jar::JarIdView, TokenAmount, U32};
-use near_sdk::{env, ext_contract, is_promise_success, json_types::U128, near_bindgen, AccountId, PromiseOrValue};
+use model::{
+    jar::{JarIdView, JarView},
+    TokenAmount, U32,
+};
+use near_sdk::{
+    env, ext_contract, is_promise_success,
+    json_types::U128,
+    near_bindgen, require,
+    serde::{Deserialize, Serialize},
+    AccountId, PromiseOrValue,
+};
 
 use crate::{
+    assert::{assert_not_frozen, assert_not_locked},
     common::Timestamp,
     event::{emit, ClaimEventItem, EventKind},
-    jar::model::Jar,
-    Contract, ContractExt, JarsStorage,
+    history::model::JarActivityKind,
+    jar::{
+        api::MAX_PAGE_LIMIT,
+        model::{Jar, JarFilter, JarTicket, LockKind},
+        view::ClaimPageView,
+    },
+    operator::model::OperatorAction,
+    Base64VecU8, Contract, ContractExt,
 };
 
 /// The `ClaimApi` trait defines methods for claiming interest from jars within the smart contract.
@@ -20,6 +36,36 @@ pub trait ClaimApi {
     /// interest across all jars is zero, the returned value will also be zero.
     fn claim_total(&mut self) -> PromiseOrValue<U128>;
 
+    /// Claims all available interest from a page of deposit jars belonging to the calling account.
+    /// Jars are ordered by ID the same way as in `get_jars_for_account_paged`. Accounts with many jars
+    /// should claim their interest page by page, since `claim_total` processes all the jars in a single call.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - An optional `JarFilter` describing which jars to claim interest from.
+    /// * `from_index` - An optional ID of the first jar of the page.
+    /// * `limit` - An optional maximum number of jars on the page. It can't exceed `MAX_PAGE_LIMIT`.
+    ///
+    /// # Returns
+    ///
+    /// A `PromiseOrValue<ClaimPageView>` containing the amount of tokens claimed and the ID of the first jar
+    /// of the next page to pass as `from_index` to the next call, if there is one.
+    fn claim_total_paged(
+        &mut self,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+    ) -> PromiseOrValue<ClaimPageView>;
+
+    /// Returns the number of `claim_total_paged` calls required to claim interest from all jars of an account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jars owner.
+    /// * `filter` - An optional `JarFilter` describing which jars to claim interest from.
+    /// * `limit` - An optional maximum number of jars on a page. It can't exceed `MAX_PAGE_LIMIT`.
+    fn get_claim_batches_count(&self, account_id: AccountId, filter: Option<JarFilter>, limit: Option<u32>) -> u32;
+
     /// Claims interest from specific deposit jars with provided IDs.
     ///
     /// # Arguments
@@ -29,12 +75,71 @@ pub trait ClaimApi {
     ///              will attempt to claim this specific amount of tokens. If not provided or if the specified amount
     ///              is greater than the total available interest in the provided jars, the method will claim the maximum
     ///              available amount.
+    /// * `account_id` - An optional `AccountId` of the jars owner. It allows an approved operator to claim
+    ///                  on behalf of the owner. Tokens claimed by an operator are always transferred to the owner.
+    /// * `receiver_id` - An optional `AccountId` of the account receiving the claimed tokens. Defaults to the owner.
+    /// * `msg` - An optional message. If it's provided, tokens are transferred with `ft_transfer_call`
+    ///           and the amount refunded by the receiver returns to the jars as unclaimed interest.
     ///
     /// # Returns
     ///
     /// A `PromiseOrValue<TokenAmount>` representing the amount of tokens claimed. If the total available interest
     /// across the specified jars is zero or the provided `amount` is zero, the returned value will also be zero.
-    fn claim_jars(&mut self, jar_ids: Vec<JarIdView>, amount: Option<U128>) -> PromiseOrValue<U128>;
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If `account_id` is provided and the caller isn't an operator of this account allowed to claim.
+    /// - If `receiver_id` or `msg` is provided by an operator.
+    /// - If the owner or the receiver is blocked.
+    fn claim_jars(
+        &mut self,
+        jar_ids: Vec<JarIdView>,
+        amount: Option<U128>,
+        account_id: Option<AccountId>,
+        receiver_id: Option<AccountId>,
+        msg: Option<String>,
+    ) -> PromiseOrValue<U128>;
+
+    /// Claims interest from specific deposit jars and moves it into principal of another jar.
+    ///
+    /// Since the claimed tokens never leave the contract, the operation is completed synchronously.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - A `Vec<JarId>` containing the IDs of the deposit jars from which interest is being claimed.
+    /// * `amount` - An optional `TokenAmount` specifying the desired amount of tokens to claim. It has the same
+    ///              meaning as in `claim_jars`. Since a ticket signature covers the amount of a new jar,
+    ///              it's required to create a jar for a product with a public key.
+    /// * `target` - A `ClaimTarget` describing where the claimed interest goes: either an existing jar
+    ///              to top up or a product ticket to create a new jar.
+    ///
+    /// # Returns
+    ///
+    /// A `JarView` containing details about the jar which received the claimed interest.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If there is no interest to claim in the specified jars.
+    /// - If the target jar doesn't belong to the caller, its product doesn't allow top-ups
+    ///   or another operation on it is in progress.
+    /// - If a new jar can't be created for the provided ticket and the claimed amount.
+    fn claim_to_jar(&mut self, jar_ids: Vec<JarIdView>, amount: Option<U128>, target: ClaimTarget) -> JarView;
+}
+
+/// The `ClaimTarget` enum describes a jar which receives claimed interest in `claim_to_jar`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde", tag = "type", content = "data", rename_all = "snake_case")]
+pub enum ClaimTarget {
+    /// An existing jar of the caller to top up.
+    Jar(JarIdView),
+
+    /// A product ticket to create a new jar.
+    Product {
+        ticket: JarTicket,
+        signature: Option<Base64VecU8>,
+    },
 }
 
 #[ext_contract(ext_self)]
@@ -42,138 +147,428 @@ pub trait ClaimCallbacks {
     fn after_claim(
         &mut self,
         claimed_amount: U128,
+        lock_id: u64,
         jars_before_transfer: Vec<Jar>,
         event: EventKind,
         now: Timestamp,
+        is_transfer_call: bool,
     ) -> U128;
+
+    fn after_claim_page(
+        &mut self,
+        claimed_amount: U128,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event: EventKind,
+        now: Timestamp,
+        next_index: Option<JarIdView>,
+    ) -> ClaimPageView;
 }
 
 #[near_bindgen]
 impl ClaimApi for Contract {
     fn claim_total(&mut self) -> PromiseOrValue<U128> {
         let account_id = env::predecessor_account_id();
-        let jar_ids = self.account_jars(&account_id).iter().map(|a| U32(a.id)).collect();
-        self.claim_jars(jar_ids, None)
+        let jar_ids = self.account_jar_ids(&account_id).into_iter().map(U32).collect();
+        self.claim_jars(jar_ids, None, None, None, None)
     }
 
-    fn claim_jars(&mut self, jar_ids: Vec<JarIdView>, amount: Option<U128>) -> PromiseOrValue<U128> {
+    fn claim_total_paged(
+        &mut self,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+    ) -> PromiseOrValue<ClaimPageView> {
         let account_id = env::predecessor_account_id();
         let now = env::block_timestamp_ms();
 
-        let unlocked_jars: Vec<Jar> = self
-            .account_jars(&account_id)
-            .iter()
-            .filter(|jar| !jar.is_pending_withdraw && jar_ids.contains(&U32(jar.id)))
-            .cloned()
-            .collect();
+        self.assert_not_blocked(&account_id);
 
-        let mut total_interest_to_claim: TokenAmount = 0;
+        let (jars, next_index) = self.get_jars_page(&account_id, filter, from_index, limit, now);
+        let jar_ids: Vec<_> = jars.iter().map(|jar| U32(jar.id)).collect();
 
-        let mut event_data: Vec<ClaimEventItem> = vec![];
+        let (lock_id, unlocked_jars, total_interest_to_claim, event_data) =
+            self.claim_and_lock_jars(&account_id, &jar_ids, None, now);
+
+        if total_interest_to_claim > 0 {
+            self.claim_page_interest(
+                &account_id,
+                U128(total_interest_to_claim),
+                lock_id,
+                unlocked_jars,
+                EventKind::Claim(event_data),
+                now,
+                next_index,
+            )
+        } else {
+            PromiseOrValue::Value(ClaimPageView {
+                claimed_amount: U128(0),
+                next_index,
+            })
+        }
+    }
 
-        for jar in &unlocked_jars {
-            let product = self.get_product(&jar.product_id);
-            let available_interest = jar.get_interest(product, now);
-            let interest_to_claim = amount.map_or(available_interest, |amount| {
-                cmp::min(available_interest, amount.0 - total_interest_to_claim)
-            });
+    fn get_claim_batches_count(&self, account_id: AccountId, filter: Option<JarFilter>, limit: Option<u32>) -> u32 {
+        let filter = filter.unwrap_or_default();
+        let limit = limit.map_or(MAX_PAGE_LIMIT, |limit| limit.min(MAX_PAGE_LIMIT));
+        let now = env::block_timestamp_ms();
 
-            if interest_to_claim > 0 {
-                self.get_jar_mut_internal(&jar.account_id, jar.id)
-                    .claim(available_interest, interest_to_claim, now)
-                    .lock();
+        require!(limit > 0, "Page limit must be positive");
 
-                total_interest_to_claim += interest_to_claim;
+        let Some(account_jars) = self.account_jars.get(&account_id) else {
+            return 0;
+        };
 
-                event_data.push(ClaimEventItem {
-                    id: jar.id,
-                    interest_to_claim: U128(interest_to_claim),
-                });
-            }
-        }
+        let jars_count = if filter.is_empty() {
+            account_jars.jar_ids.len()
+        } else {
+            account_jars
+                .jar_ids
+                .iter()
+                .map(|id| self.get_stored_jar(*id))
+                .filter(|jar| filter.matches(jar, self.get_product(&jar.product_id), now))
+                .count()
+        };
+
+        u32::try_from(jars_count).unwrap_or(u32::MAX).div_ceil(limit)
+    }
+
+    fn claim_jars(
+        &mut self,
+        jar_ids: Vec<JarIdView>,
+        amount: Option<U128>,
+        account_id: Option<AccountId>,
+        receiver_id: Option<AccountId>,
+        msg: Option<String>,
+    ) -> PromiseOrValue<U128> {
+        let account_id = self.get_account_for_action(account_id, OperatorAction::Claim);
+        self.assert_not_blocked(&account_id);
+        let receiver_id = self.get_transfer_receiver(&account_id, receiver_id, &msg);
+
+        let now = env::block_timestamp_ms();
+
+        let (lock_id, unlocked_jars, total_interest_to_claim, event_data) =
+            self.claim_and_lock_jars(&account_id, &jar_ids, amount, now);
 
         if total_interest_to_claim > 0 {
             self.claim_interest(
-                &account_id,
+                &receiver_id,
                 U128(total_interest_to_claim),
+                lock_id,
                 unlocked_jars,
                 EventKind::Claim(event_data),
                 now,
+                msg,
             )
         } else {
             PromiseOrValue::Value(U128(0))
         }
     }
+
+    fn claim_to_jar(&mut self, jar_ids: Vec<JarIdView>, amount: Option<U128>, target: ClaimTarget) -> JarView {
+        let account_id = env::predecessor_account_id();
+        let now = env::block_timestamp_ms();
+
+        self.assert_not_blocked(&account_id);
+
+        if let ClaimTarget::Jar(target_id) = &target {
+            let target_jar = self.get_jar_internal(&account_id, target_id.0);
+            assert_not_locked(target_jar);
+            assert_not_frozen(target_jar);
+        }
+
+        let (claimed_jars, total_interest_to_claim, event_data) =
+            self.claim_from_jars(&account_id, &jar_ids, amount, now);
+
+        require!(total_interest_to_claim > 0, "There is no interest to claim");
+
+        emit(EventKind::Claim(event_data));
+
+        let target_jar = match target {
+            ClaimTarget::Jar(target_id) => {
+                self.top_up(&account_id, target_id.0, U128(total_interest_to_claim));
+                self.get_jar_internal(&account_id, target_id.0).into()
+            }
+            ClaimTarget::Product { ticket, signature } => {
+                self.create_jar(account_id.clone(), ticket, U128(total_interest_to_claim), signature)
+            }
+        };
+
+        for jar_before_claim in claimed_jars {
+            let jar = self.get_jar_internal(&account_id, jar_before_claim.id);
+            let claimed = jar.claimed_balance - jar_before_claim.claimed_balance;
+            let should_be_closed = jar.should_be_closed(self.get_product(&jar.product_id), now);
+
+            if claimed > 0 {
+                self.record_activity(jar_before_claim.id, JarActivityKind::Claim, claimed);
+            }
+
+            if should_be_closed {
+                self.delete_jar(&account_id, jar_before_claim.id);
+            }
+        }
+
+        target_jar
+    }
 }
 
 impl Contract {
+    /// Claims interest from unlocked jars with provided IDs and locks the claimed jars until the transfer completes.
+    ///
+    /// Returns the ID of the lock, the jars as they were before claiming, the total claimed amount
+    /// and claim event items.
+    fn claim_and_lock_jars(
+        &mut self,
+        account_id: &AccountId,
+        jar_ids: &[JarIdView],
+        amount: Option<U128>,
+        now: Timestamp,
+    ) -> (u64, Vec<Jar>, TokenAmount, Vec<ClaimEventItem>) {
+        let (unlocked_jars, total_interest_to_claim, event_data) =
+            self.claim_from_jars(account_id, jar_ids, amount, now);
+        let lock_id = self.increment_and_get_last_lock_id();
+
+        for jar in unlocked_jars
+            .iter()
+            .filter(|jar| event_data.iter().any(|item| item.id == jar.id))
+        {
+            self.get_jar_mut_internal(account_id, jar.id)
+                .lock(lock_id, LockKind::Claim, jar, 0, now);
+        }
+
+        (lock_id, unlocked_jars, total_interest_to_claim, event_data)
+    }
+
+    /// Claims interest from unlocked jars with provided IDs without transferring it anywhere.
+    ///
+    /// Returns the jars as they were before claiming, the total claimed amount and claim event items.
+    fn claim_from_jars(
+        &mut self,
+        account_id: &AccountId,
+        jar_ids: &[JarIdView],
+        amount: Option<U128>,
+        now: Timestamp,
+    ) -> (Vec<Jar>, TokenAmount, Vec<ClaimEventItem>) {
+        let claimable_jars = self.get_claimable_jars(account_id, jar_ids, amount, now);
+
+        let mut unlocked_jars: Vec<Jar> = vec![];
+        let mut total_interest_to_claim: TokenAmount = 0;
+
+        let mut event_data: Vec<ClaimEventItem> = vec![];
+
+        for (jar, available_interest, interest_to_claim) in claimable_jars {
+            if interest_to_claim > 0 {
+                self.get_jar_mut_internal(&jar.account_id, jar.id)
+                    .claim(available_interest, interest_to_claim, now);
+
+                total_interest_to_claim += interest_to_claim;
+
+                event_data.push(ClaimEventItem {
+                    id: jar.id,
+                    interest_to_claim: U128(interest_to_claim),
+                });
+            }
+
+            unlocked_jars.push(jar);
+        }
+
+        (unlocked_jars, total_interest_to_claim, event_data)
+    }
+
+    /// Calculates interest to claim from unlocked jars with provided IDs.
+    ///
+    /// Returns every unlocked jar along with its available interest and the part of it to claim.
+    /// The amount to claim is taken from the jars in the order of their IDs.
+    pub(crate) fn get_claimable_jars(
+        &self,
+        account_id: &AccountId,
+        jar_ids: &[JarIdView],
+        amount: Option<U128>,
+        now: Timestamp,
+    ) -> Vec<(Jar, TokenAmount, TokenAmount)> {
+        let mut total_interest_to_claim: TokenAmount = 0;
+
+        self.find_account_jars_with_ids(account_id, jar_ids)
+            .into_iter()
+            .filter(|jar| !jar.is_pending_withdraw && !jar.is_frozen())
+            .map(|jar| {
+                let product = self.get_product(&jar.product_id);
+                let available_interest = jar.get_interest(product, now);
+                let interest_to_claim = amount.map_or(available_interest, |amount| {
+                    cmp::min(available_interest, amount.0 - total_interest_to_claim)
+                });
+
+                total_interest_to_claim += interest_to_claim;
+
+                (jar.clone(), available_interest, interest_to_claim)
+            })
+            .collect()
+    }
+
     #[cfg(test)]
     fn claim_interest(
         &mut self,
-        _account_id: &AccountId,
+        _receiver_id: &AccountId,
         claimed_amount: U128,
+        lock_id: u64,
         jars_before_transfer: Vec<Jar>,
         event: EventKind,
         now: Timestamp,
+        msg: Option<String>,
     ) -> PromiseOrValue<U128> {
         PromiseOrValue::Value(self.after_claim_internal(
             claimed_amount,
+            lock_id,
             jars_before_transfer,
             event,
             now,
             crate::common::test_data::get_test_future_success(),
+            crate::common::test_data::get_test_future_refund(msg.is_some()),
         ))
     }
 
+    #[cfg(test)]
+    fn claim_page_interest(
+        &mut self,
+        _account_id: &AccountId,
+        claimed_amount: U128,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event: EventKind,
+        now: Timestamp,
+        next_index: Option<JarIdView>,
+    ) -> PromiseOrValue<ClaimPageView> {
+        PromiseOrValue::Value(ClaimPageView {
+            claimed_amount: self.after_claim_internal(
+                claimed_amount,
+                lock_id,
+                jars_before_transfer,
+                event,
+                now,
+                crate::common::test_data::get_test_future_success(),
+                0,
+            ),
+            next_index,
+        })
+    }
+
     #[cfg(not(test))]
     #[mutants::skip] // Covered by integration tests
-    fn claim_interest(
+    fn claim_page_interest(
         &mut self,
         account_id: &AccountId,
         claimed_amount: U128,
+        lock_id: u64,
         jars_before_transfer: Vec<Jar>,
         event: EventKind,
         now: Timestamp,
+        next_index: Option<JarIdView>,
+    ) -> PromiseOrValue<ClaimPageView> {
+        use crate::ft_interface::FungibleTokenInterface;
+
+        let gas = crate::common::gas_data::gas_for_after_claim(jars_before_transfer.len());
+
+        self.ft_contract()
+            .transfer(account_id, claimed_amount.0, "claim", &None, None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(gas)
+                    .after_claim_page(claimed_amount, lock_id, jars_before_transfer, event, now, next_index),
+            )
+            .into()
+    }
+
+    #[cfg(not(test))]
+    #[mutants::skip] // Covered by integration tests
+    fn claim_interest(
+        &mut self,
+        receiver_id: &AccountId,
+        claimed_amount: U128,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event: EventKind,
+        now: Timestamp,
+        msg: Option<String>,
     ) -> PromiseOrValue<U128> {
         use crate::ft_interface::FungibleTokenInterface;
+
+        let is_transfer_call = msg.is_some();
+
         self.ft_contract()
-            .transfer(account_id, claimed_amount.0, "claim", &None)
-            .then(after_claim_call(claimed_amount, jars_before_transfer, event, now))
+            .transfer(receiver_id, claimed_amount.0, "claim", &None, msg)
+            .then(after_claim_call(
+                claimed_amount,
+                lock_id,
+                jars_before_transfer,
+                event,
+                now,
+                is_transfer_call,
+            ))
             .into()
     }
 
+    /// Completes a claim. `refund` is the amount returned by the receiver of `ft_transfer_call`,
+    /// it goes back to the jars as unclaimed interest in the order they were claimed.
+    /// Jars which aren't held by the lock of the claim anymore are left untouched.
     fn after_claim_internal(
         &mut self,
         claimed_amount: U128,
+        lock_id: u64,
         jars_before_transfer: Vec<Jar>,
-        event: EventKind,
+        mut event: EventKind,
         now: Timestamp,
         is_promise_success: bool,
+        refund: TokenAmount,
     ) -> U128 {
+        let jars_before_transfer: Vec<Jar> = jars_before_transfer
+            .into_iter()
+            .filter(|jar| self.is_locked_by(jar.id, lock_id))
+            .collect();
+
+        if jars_before_transfer.is_empty() {
+            return U128(0);
+        }
+
         if is_promise_success {
-            for jar_before_transfer in jars_before_transfer {
-                let product = self.products.get(&jar_before_transfer.product_id).unwrap_or_else(|| {
-                    env::panic_str(&format!("Product '{}' doesn't exist", jar_before_transfer.product_id))
-                });
+            let mut remaining_refund = refund;
 
-                let jar = self
-                    .account_jars
-                    .get_mut(&jar_before_transfer.account_id)
-                    .unwrap_or_else(|| {
-                        env::panic_str(&format!("Account '{}' doesn't exist", jar_before_transfer.account_id))
-                    })
-                    .get_jar_mut(jar_before_transfer.id);
+            for jar_before_transfer in jars_before_transfer {
+                let account_id = &jar_before_transfer.account_id;
 
+                let jar = self.get_jar_mut_internal(account_id, jar_before_transfer.id);
                 jar.unlock();
 
-                if jar.should_be_closed(product, now) {
-                    self.delete_jar(&jar_before_transfer.account_id, jar_before_transfer.id);
+                let jar_refund = cmp::min(
+                    jar.claimed_balance - jar_before_transfer.claimed_balance,
+                    remaining_refund,
+                );
+                if jar_refund > 0 {
+                    jar.revert_claim(jar_refund);
+                    remaining_refund -= jar_refund;
+
+                    if let EventKind::Claim(items) = &mut event {
+                        if let Some(item) = items.iter_mut().find(|item| item.id == jar_before_transfer.id) {
+                            item.interest_to_claim.0 -= jar_refund;
+                        }
+                    }
+                }
+
+                let jar = self.get_jar_internal(account_id, jar_before_transfer.id);
+                let claimed = jar.claimed_balance - jar_before_transfer.claimed_balance;
+                let should_be_closed = jar.should_be_closed(self.get_product(&jar.product_id), now);
+
+                if claimed > 0 {
+                    self.record_activity(jar_before_transfer.id, JarActivityKind::Claim, claimed);
+                }
+
+                if should_be_closed {
+                    self.close_jar(account_id, jar_before_transfer.id);
                 }
             }
 
             emit(event);
 
-            claimed_amount
+            U128(claimed_amount.0 - refund)
         } else {
             for jar_before_transfer in jars_before_transfer {
                 let account_id = jar_before_transfer.account_id.clone();
@@ -193,11 +588,47 @@ impl ClaimCallbacks for Contract {
     fn after_claim(
         &mut self,
         claimed_amount: U128,
+        lock_id: u64,
         jars_before_transfer: Vec<Jar>,
         event: EventKind,
         now: Timestamp,
+        is_transfer_call: bool,
     ) -> U128 {
-        self.after_claim_internal(claimed_amount, jars_before_transfer, event, now, is_promise_success())
+        let refund = crate::ft_interface::get_transfer_refund(claimed_amount.0, is_transfer_call);
+
+        self.after_claim_internal(
+            claimed_amount,
+            lock_id,
+            jars_before_transfer,
+            event,
+            now,
+            is_promise_success(),
+            refund,
+        )
+    }
+
+    #[private]
+    fn after_claim_page(
+        &mut self,
+        claimed_amount: U128,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event: EventKind,
+        now: Timestamp,
+        next_index: Option<JarIdView>,
+    ) -> ClaimPageView {
+        ClaimPageView {
+            claimed_amount: self.after_claim_internal(
+                claimed_amount,
+                lock_id,
+                jars_before_transfer,
+                event,
+                now,
+                is_promise_success(),
+                0,
+            ),
+            next_index,
+        }
     }
 }
 
@@ -205,11 +636,22 @@ impl ClaimCallbacks for Contract {
 #[mutants::skip] // Covered by integration tests
 fn after_claim_call(
     claimed_amount: U128,
+    lock_id: u64,
     jars_before_transfer: Vec<Jar>,
     event: EventKind,
     now: Timestamp,
+    is_transfer_call: bool,
 ) -> crate::Promise {
+    let gas = crate::common::gas_data::gas_for_after_claim(jars_before_transfer.len());
+
     ext_self::ext(env::current_account_id())
-        .with_static_gas(crate::common::gas_data::GAS_FOR_AFTER_CLAIM)
-        .after_claim(claimed_amount, jars_before_transfer, event, now)
+        .with_static_gas(gas)
+        .after_claim(
+            claimed_amount,
+            lock_id,
+            jars_before_transfer,
+            event,
+            now,
+            is_transfer_call,
+        )
 }
@@ -1,12 +1,24 @@
 #![cfg(test)]
 
 use model::U32;
-use near_sdk::{json_types::U128, test_utils::accounts, PromiseOrValue};
+use near_sdk::{
+    json_types::{U128, U64},
+    test_utils::accounts,
+    PromiseOrValue,
+};
 
 use crate::{
-    claim::api::ClaimApi,
-    common::{test_data::set_test_future_success, tests::Context, udecimal::UDecimal, MS_IN_YEAR},
-    jar::{api::JarApi, model::Jar},
+    claim::api::{ClaimApi, ClaimTarget},
+    common::{
+        test_data::{set_test_future_refund, set_test_future_success},
+        tests::Context,
+        udecimal::UDecimal,
+        MS_IN_YEAR,
+    },
+    jar::{
+        api::JarApi,
+        model::{Jar, JarTicket},
+    },
     product::model::{Apy, Product},
     withdraw::api::WithdrawApi,
 };
@@ -42,7 +54,11 @@ fn claim_partially_when_having_tokens_to_claim() {
     context.set_block_timestamp_in_days(365);
 
     context.switch_account(&alice);
-    let PromiseOrValue::Value(claimed) = context.contract.claim_jars(vec![U32(jar.id)], Some(U128(100))) else {
+    let PromiseOrValue::Value(claimed) =
+        context
+            .contract
+            .claim_jars(vec![U32(jar.id)], Some(U128(100)), None, None, None)
+    else {
         panic!()
     };
 
@@ -52,6 +68,39 @@ fn claim_partially_when_having_tokens_to_claim() {
     assert_eq!(100, jar.claimed_balance.0);
 }
 
+#[test]
+fn claim_with_transfer_call_refund() {
+    set_test_future_refund(50_000);
+
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product().apy(Apy::Constant(UDecimal::new(2, 1)));
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+    context.set_block_timestamp_in_days(365);
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(claimed) = context.contract.claim_jars(
+        vec![U32(jar.id)],
+        None,
+        None,
+        Some(accounts(3)),
+        Some("swap".to_string()),
+    ) else {
+        panic!()
+    };
+
+    assert_eq!(claimed.0, 150_000);
+
+    let jar = context.contract.get_jar_internal(&alice, jar.id);
+    assert_eq!(150_000, jar.claimed_balance);
+
+    let Some(ref cache) = jar.cache else { panic!() };
+    assert_eq!(50_000, cache.interest);
+}
+
 #[test]
 fn dont_delete_jar_on_all_interest_claim() {
     let alice = accounts(0);
@@ -64,7 +113,9 @@ fn dont_delete_jar_on_all_interest_claim() {
     context.set_block_timestamp_in_days(365);
 
     context.switch_account(&alice);
-    context.contract.claim_jars(vec![U32(jar.id)], Some(U128(200_000)));
+    context
+        .contract
+        .claim_jars(vec![U32(jar.id)], Some(U128(200_000)), None, None, None);
 
     let jar = context.contract.get_jar_internal(&alice, jar.id);
     assert_eq!(200_000, jar.claimed_balance);
@@ -76,7 +127,7 @@ fn dont_delete_jar_on_all_interest_claim() {
 }
 
 #[test]
-#[should_panic(expected = "Jar with id: 0 doesn't exist")]
+#[should_panic(expected = "Account 'alice' doesn't exist")]
 fn claim_all_withdraw_all_and_delete_jar() {
     let alice = accounts(0);
     let admin = accounts(1);
@@ -93,7 +144,11 @@ fn claim_all_withdraw_all_and_delete_jar() {
     context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
 
     context.switch_account(&alice);
-    let PromiseOrValue::Value(claimed) = context.contract.claim_jars(vec![U32(jar_id)], Some(U128(200_000))) else {
+    let PromiseOrValue::Value(claimed) =
+        context
+            .contract
+            .claim_jars(vec![U32(jar_id)], Some(U128(200_000)), None, None, None)
+    else {
         panic!()
     };
 
@@ -107,7 +162,7 @@ fn claim_all_withdraw_all_and_delete_jar() {
     assert_eq!(cache.interest, 0);
     assert_eq!(jar.principal, 1_000_000);
 
-    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(jar_id), None) else {
+    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(jar_id), None, None, None, None) else {
         panic!()
     };
 
@@ -118,7 +173,7 @@ fn claim_all_withdraw_all_and_delete_jar() {
 }
 
 #[test]
-#[should_panic(expected = "Jar with id: 0 doesn't exist")]
+#[should_panic(expected = "Account 'alice' doesn't exist")]
 fn withdraw_all_claim_all_and_delete_jar() {
     let alice = accounts(0);
     let admin = accounts(1);
@@ -136,7 +191,7 @@ fn withdraw_all_claim_all_and_delete_jar() {
 
     context.switch_account(&alice);
 
-    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(jar_id), None) else {
+    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(jar_id), None, None, None, None) else {
         panic!()
     };
 
@@ -147,7 +202,11 @@ fn withdraw_all_claim_all_and_delete_jar() {
 
     assert_eq!(jar.principal, 0);
 
-    let PromiseOrValue::Value(claimed) = context.contract.claim_jars(vec![U32(jar_id)], Some(U128(200_000))) else {
+    let PromiseOrValue::Value(claimed) =
+        context
+            .contract
+            .claim_jars(vec![U32(jar_id)], Some(U128(200_000)), None, None, None)
+    else {
         panic!();
     };
 
@@ -173,7 +232,11 @@ fn failed_future_claim() {
 
     let jar_before_claim = context.contract.get_jar_internal(&alice, jar.id).clone();
 
-    let PromiseOrValue::Value(claimed) = context.contract.claim_jars(vec![U32(jar.id)], Some(U128(200_000))) else {
+    let PromiseOrValue::Value(claimed) =
+        context
+            .contract
+            .claim_jars(vec![U32(jar.id)], Some(U128(200_000)), None, None, None)
+    else {
         panic!()
     };
 
@@ -184,6 +247,159 @@ fn failed_future_claim() {
     assert_eq!(&jar_before_claim, jar_after_claim);
 }
 
+#[test]
+fn claim_to_existing_jar() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product().cap(0, 100_000_000).with_allows_top_up(true);
+    let jars = [
+        Jar::generate(0, &alice, &product.id).principal(1_000_000),
+        Jar::generate(1, &alice, &product.id).principal(1_000_000),
+    ];
+    let mut context = Context::new(admin)
+        .with_products(&[product])
+        .with_jars(&jars)
+        .with_storage_deposit(&[alice.clone()]);
+
+    context.set_block_timestamp_in_days(365);
+
+    context.switch_account(&alice);
+    let target_jar = context
+        .contract
+        .claim_to_jar(vec![U32(0)], None, ClaimTarget::Jar(U32(1)));
+
+    assert_eq!(1, target_jar.id.0);
+    assert_eq!(1_120_000, target_jar.principal.0);
+    assert_eq!(
+        120_000,
+        context.contract.get_jar(alice.clone(), U32(0)).claimed_balance.0
+    );
+    assert!(!context.contract.get_jar_internal(&alice, 0).is_pending_withdraw);
+}
+
+#[test]
+fn claim_to_new_jar() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product().cap(0, 100_000_000);
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let mut context = Context::new(admin)
+        .with_products(&[product.clone()])
+        .with_jars(&[jar.clone()])
+        .with_storage_deposit(&[alice.clone()]);
+
+    context.set_block_timestamp_in_days(365);
+
+    context.switch_account(&alice);
+    let ticket = JarTicket {
+        product_id: product.id,
+        valid_until: U64(MS_IN_YEAR * 2),
+    };
+    let new_jar = context.contract.claim_to_jar(
+        vec![U32(jar.id)],
+        None,
+        ClaimTarget::Product {
+            ticket,
+            signature: None,
+        },
+    );
+
+    assert_eq!(120_000, new_jar.principal.0);
+    assert_eq!(2, context.contract.get_jars_for_account(alice).len());
+}
+
+#[test]
+#[should_panic(expected = "There is no interest to claim")]
+fn claim_to_jar_when_nothing_to_claim() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product().with_allows_top_up(true);
+    let jars = [
+        Jar::generate(0, &alice, &product.id).principal(1_000_000),
+        Jar::generate(1, &alice, &product.id).principal(1_000_000),
+    ];
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&jars);
+
+    context.switch_account(&alice);
+    context
+        .contract
+        .claim_to_jar(vec![U32(0)], None, ClaimTarget::Jar(U32(1)));
+}
+
+#[test]
+fn claim_total_paged_claims_only_jars_of_the_page() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product();
+    let jars: Vec<Jar> = [2, 0, 1]
+        .into_iter()
+        .map(|id| Jar::generate(id, &alice, &product.id).principal(100_000_000))
+        .collect();
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&jars);
+
+    context.set_block_timestamp_in_days(365);
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(page) = context.contract.claim_total_paged(None, Some(U32(1)), Some(1)) else {
+        panic!("Invalid promise type");
+    };
+    assert_eq!(12_000_000, page.claimed_amount.0);
+    assert_eq!(Some(U32(2)), page.next_index);
+
+    let interest = context.contract.get_total_interest(alice).amount.detailed;
+    assert_eq!(12_000_000, interest[&U32(0)].0);
+    assert_eq!(0, interest[&U32(1)].0);
+    assert_eq!(12_000_000, interest[&U32(2)].0);
+}
+
+#[test]
+fn claim_total_paged_resumes_from_cursor() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product();
+    let jars: Vec<Jar> = (0..5)
+        .map(|id| Jar::generate(id, &alice, &product.id).principal(100_000_000))
+        .collect();
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&jars);
+
+    context.set_block_timestamp_in_days(365);
+
+    assert_eq!(
+        3,
+        context.contract.get_claim_batches_count(alice.clone(), None, Some(2))
+    );
+    assert_eq!(1, context.contract.get_claim_batches_count(alice.clone(), None, None));
+
+    context.switch_account(&alice);
+
+    let mut from_index = None;
+    let mut batches = 0;
+    let mut total_claimed = 0;
+
+    loop {
+        let PromiseOrValue::Value(page) = context.contract.claim_total_paged(None, from_index, Some(2)) else {
+            panic!("Invalid promise type");
+        };
+
+        batches += 1;
+        total_claimed += page.claimed_amount.0;
+        from_index = page.next_index;
+
+        if from_index.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(3, batches);
+    assert_eq!(5 * 12_000_000, total_claimed);
+    assert_eq!(0, context.contract.get_total_interest(alice).amount.total.0);
+}
+
 fn generate_product() -> Product {
     Product::generate("product")
         .enabled(true)
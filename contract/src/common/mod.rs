@@ -1,7 +1,7 @@
 pub(crate) mod test_data;
 pub(crate) mod tests;
 pub(crate) mod udecimal;
-use near_sdk::Gas;
+use near_sdk::{require, Gas};
 
 /// Milliseconds since the Unix epoch (January 1, 1970 (midnight UTC/GMT))
 pub type Timestamp = u64;
@@ -20,6 +20,41 @@ pub const fn tgas(val: u64) -> Gas {
     Gas(TERA * val)
 }
 
+/// Calculates `value * numerator / denominator` rounded down without an intermediate overflow.
+/// The result must fit into `u128` and the denominator must not exceed `u128::MAX / 2`.
+pub(crate) fn mul_div(value: u128, numerator: u128, denominator: u128) -> u128 {
+    require!(denominator != 0, "Division by zero");
+
+    if let Some(product) = value.checked_mul(numerator) {
+        return product / denominator;
+    }
+
+    let remainder = value % denominator;
+
+    // Binary long multiplication of `remainder * numerator` keeping the intermediate value below `denominator`
+    let mut quotient: u128 = 0;
+    let mut accumulated: u128 = 0;
+
+    for bit in (0..u128::BITS).rev() {
+        quotient <<= 1;
+        accumulated <<= 1;
+        if accumulated >= denominator {
+            accumulated -= denominator;
+            quotient += 1;
+        }
+
+        if (numerator >> bit) & 1 == 1 {
+            accumulated += remainder;
+            if accumulated >= denominator {
+                accumulated -= denominator;
+                quotient += 1;
+            }
+        }
+    }
+
+    value / denominator * numerator + quotient
+}
+
 #[cfg(not(test))]
 pub mod gas_data {
     use near_sdk::Gas;
@@ -34,22 +69,46 @@ pub mod gas_data {
     /// Cost of adding 1 additional jar in after claim call. Measured with `measure_after_claim_total_test`
     const ADDITIONAL_AFTER_CLAIM_JAR_COST: u64 = 80 * GIGA;
 
+    /// Gas for the after claim call processing the given number of jars.
     /// Values are measured with `measure_after_claim_total_test`
-    /// For now number of jars is arbitrary
-    pub(crate) const GAS_FOR_AFTER_CLAIM: Gas =
-        Gas(INITIAL_GAS_FOR_AFTER_CLAIM + ADDITIONAL_AFTER_CLAIM_JAR_COST * 200);
+    pub(crate) fn gas_for_after_claim(jars_count: usize) -> Gas {
+        Gas(INITIAL_GAS_FOR_AFTER_CLAIM + ADDITIONAL_AFTER_CLAIM_JAR_COST * jars_count as u64)
+    }
 
     /// Value is measured with `measure_withdraw_test`
     /// Average gas for this method call don't exceed 3.4 `TGas`. 4 here just in case.
     pub(crate) const GAS_FOR_AFTER_WITHDRAW: Gas = tgas(4);
+
+    /// The exit callback unlocks or closes every involved jar like the claim callback does,
+    /// so the same estimation is used for now.
+    pub(crate) fn gas_for_after_exit(jars_count: usize) -> Gas {
+        gas_for_after_claim(jars_count)
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::common::tgas;
+    use crate::common::{mul_div, tgas};
 
     #[test]
     fn test_gas_methods() {
         assert_eq!(tgas(50).0, 50_000_000_000_000);
     }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(100, 3, 4), 75);
+        assert_eq!(mul_div(7, 1, 2), 3);
+
+        let value = 3 * 10u128.pow(30);
+        let numerator = 2 * 10u128.pow(25);
+        let denominator = 4 * 10u128.pow(25);
+
+        assert!(value.checked_mul(numerator).is_none());
+        assert_eq!(mul_div(value, numerator, denominator), 15 * 10u128.pow(29));
+        assert_eq!(
+            mul_div(u128::MAX / 3, 10u128.pow(20) - 1, 10u128.pow(20)),
+            113_427_455_640_312_821_153_323_927_920_852_942_273
+        );
+    }
 }
@@ -22,6 +22,7 @@ static DATA: TestDataStorage = TestDataStorage {
 };
 
 const FUTURE_SUCCESS_KEY: &str = "FUTURE_SUCCESS_KEY";
+const FUTURE_REFUND_KEY: &str = "FUTURE_REFUND_KEY";
 
 fn data() -> MutexGuard<'static, Map> {
     DATA.data.lock().unwrap()
@@ -47,6 +48,25 @@ pub(crate) fn get_test_future_success() -> bool {
     value.parse().unwrap()
 }
 
+/// Sets the amount which `ft_transfer_call` receivers return to the contract in the current test.
+pub(crate) fn set_test_future_refund(amount: u128) {
+    let mut data = data();
+    let map = data.entry(thread_name()).or_default();
+    map.insert(FUTURE_REFUND_KEY.to_owned(), amount.to_string());
+}
+
+pub(crate) fn get_test_future_refund(is_transfer_call: bool) -> u128 {
+    if !is_transfer_call {
+        return 0;
+    }
+
+    let data = data();
+
+    data.get(&thread_name())
+        .and_then(|map| map.get(FUTURE_REFUND_KEY))
+        .map_or(0, |value| value.parse().unwrap())
+}
+
 fn thread_name() -> String {
     std::thread::current().name().unwrap().to_owned()
 }
@@ -2,9 +2,9 @@
 
 use std::time::Duration;
 
-use near_sdk::{test_utils::VMContextBuilder, testing_env, AccountId, Balance};
+use near_sdk::{test_utils::VMContextBuilder, testing_env, AccountId, Balance, ONE_NEAR};
 
-use crate::{jar::model::Jar, product::model::Product, Contract};
+use crate::{jar::model::Jar, product::model::Product, storage::model::AccountStorage, Contract};
 
 pub(crate) struct Context {
     pub contract: Contract,
@@ -52,7 +52,22 @@ impl Context {
                 .account_jars
                 .entry(jar.account_id.clone())
                 .or_default()
-                .push(jar.clone());
+                .insert_jar_id(jar.id);
+            self.contract.jars.insert(jar.id, jar.clone());
+            self.contract.last_jar_id = self.contract.last_jar_id.max(jar.id);
+        }
+
+        self
+    }
+
+    pub(crate) fn with_storage_deposit(mut self, account_ids: &[AccountId]) -> Self {
+        for account_id in account_ids {
+            let storage = AccountStorage {
+                deposit: ONE_NEAR,
+                sponsored: 0,
+                used_bytes: self.contract.account_storage_usage,
+            };
+            self.contract.storage_accounts.insert(account_id.clone(), storage);
         }
 
         self
@@ -86,12 +101,14 @@ impl Context {
         self.switch_account(&self.ft_contract_id.clone());
     }
 
-    pub(crate) fn with_deposit_yocto(&mut self, amount: Balance, f: impl FnOnce(&mut Context)) {
+    pub(crate) fn with_deposit_yocto<R>(&mut self, amount: Balance, f: impl FnOnce(&mut Context) -> R) -> R {
         self.set_deposit_yocto(amount);
 
-        f(self);
+        let result = f(self);
 
         self.set_deposit_yocto(0);
+
+        result
     }
 
     fn set_deposit_yocto(&mut self, amount: Balance) {
@@ -0,0 +1,187 @@
+use model::jar::{JarId, JarIdView};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::{
+    assert::require_ok,
+    common::Timestamp,
+    compliance::view::JarFreezeView,
+    event::{
+        emit, BatchBlockAccountsData, BatchFreezeJarsData, BlockAccountData,
+        EventKind::{BatchBlockAccounts, BatchFreezeJars, BlockAccount, FreezeJar},
+        FreezeJarData,
+    },
+    Contract, ContractExt,
+};
+
+/// The `ComplianceApi` trait defines methods for restricting operations of specific accounts and jars
+/// to meet legal obligations.
+pub trait ComplianceApi {
+    /// Adds an account to the blocklist or removes it from there. A blocked account can't create,
+    /// top up, receive, claim, withdraw, exit, restake or transfer jars.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the account.
+    /// * `value` - `true` to block the account, `false` to unblock it.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager.
+    fn set_blocked(&mut self, account_id: AccountId, value: bool);
+
+    /// Adds several accounts to the blocklist or removes them from there, see `set_blocked`.
+    fn batch_set_blocked(&mut self, account_ids: Vec<AccountId>, value: bool);
+
+    /// Returns `true` if the account is blocked.
+    fn is_blocked(&self, account_id: AccountId) -> bool;
+
+    /// Freezes or unfreezes a jar. Operations on a frozen jar aren't allowed. Freezing a frozen jar
+    /// replaces its freeze options.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jar owner.
+    /// * `jar_id` - The ID of the jar.
+    /// * `value` - `true` to freeze the jar, `false` to unfreeze it.
+    /// * `accrues_interest` - Whether the jar keeps yielding interest while it's frozen. Defaults to `true`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager or if the jar doesn't exist.
+    fn set_frozen(&mut self, account_id: AccountId, jar_id: JarIdView, value: bool, accrues_interest: Option<bool>);
+
+    /// Freezes or unfreezes several jars, see `set_frozen`.
+    fn batch_set_frozen(&mut self, jars: Vec<(AccountId, Vec<JarIdView>)>, value: bool, accrues_interest: Option<bool>);
+
+    /// Returns the freeze of a jar, or `None` if the jar isn't frozen.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the jar doesn't exist.
+    fn get_jar_freeze(&self, account_id: AccountId, jar_id: JarIdView) -> Option<JarFreezeView>;
+}
+
+#[near_bindgen]
+impl ComplianceApi for Contract {
+    fn set_blocked(&mut self, account_id: AccountId, value: bool) {
+        self.assert_manager();
+
+        self.set_blocked_internal(&account_id, value);
+
+        emit(BlockAccount(BlockAccountData {
+            account_id,
+            is_blocked: value,
+        }));
+    }
+
+    fn batch_set_blocked(&mut self, account_ids: Vec<AccountId>, value: bool) {
+        self.assert_manager();
+
+        for account_id in &account_ids {
+            self.set_blocked_internal(account_id, value);
+        }
+
+        emit(BatchBlockAccounts(BatchBlockAccountsData {
+            account_ids,
+            is_blocked: value,
+        }));
+    }
+
+    fn is_blocked(&self, account_id: AccountId) -> bool {
+        self.blocked_accounts.contains(&account_id)
+    }
+
+    fn set_frozen(&mut self, account_id: AccountId, jar_id: JarIdView, value: bool, accrues_interest: Option<bool>) {
+        self.assert_manager();
+
+        let jar_id = jar_id.0;
+        let accrues_interest = accrues_interest.unwrap_or(true);
+        let now = env::block_timestamp_ms();
+
+        self.set_frozen_internal(&account_id, jar_id, value, accrues_interest, now);
+
+        emit(FreezeJar(FreezeJarData {
+            id: jar_id,
+            is_frozen: value,
+            accrues_interest,
+            timestamp: now,
+        }));
+    }
+
+    fn batch_set_frozen(
+        &mut self,
+        jars: Vec<(AccountId, Vec<JarIdView>)>,
+        value: bool,
+        accrues_interest: Option<bool>,
+    ) {
+        self.assert_manager();
+
+        let accrues_interest = accrues_interest.unwrap_or(true);
+        let now = env::block_timestamp_ms();
+        let mut frozen_jars = vec![];
+
+        for (account_id, jar_ids) in jars {
+            for jar_id in jar_ids {
+                self.set_frozen_internal(&account_id, jar_id.0, value, accrues_interest, now);
+                frozen_jars.push(jar_id.0);
+            }
+        }
+
+        emit(BatchFreezeJars(BatchFreezeJarsData {
+            jars: frozen_jars,
+            is_frozen: value,
+            accrues_interest,
+            timestamp: now,
+        }));
+    }
+
+    fn get_jar_freeze(&self, account_id: AccountId, jar_id: JarIdView) -> Option<JarFreezeView> {
+        self.get_jar_internal(&account_id, jar_id.0)
+            .freeze
+            .as_ref()
+            .map(Into::into)
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_not_blocked(&self, account_id: &AccountId) {
+        require_ok(self.check_not_blocked(account_id));
+    }
+
+    /// Checks whether an account is allowed to act with its jars. Returns the reason if it isn't.
+    pub(crate) fn check_not_blocked(&self, account_id: &AccountId) -> Result<(), String> {
+        if self.blocked_accounts.contains(account_id) {
+            return Err(format!("Account '{account_id}' is blocked"));
+        }
+
+        Ok(())
+    }
+
+    fn set_blocked_internal(&mut self, account_id: &AccountId, value: bool) {
+        if value {
+            self.blocked_accounts.insert(account_id.clone());
+        } else {
+            self.blocked_accounts.remove(account_id);
+        }
+    }
+
+    fn set_frozen_internal(
+        &mut self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        value: bool,
+        accrues_interest: bool,
+        now: Timestamp,
+    ) {
+        let product = self
+            .get_product(&self.get_jar_internal(account_id, jar_id).product_id)
+            .clone();
+        let jar = self.get_jar_mut_internal(account_id, jar_id);
+
+        jar.unfreeze(&product, now);
+
+        if value {
+            jar.freeze(accrues_interest, now);
+        }
+    }
+}
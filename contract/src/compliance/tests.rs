@@ -0,0 +1,215 @@
+#![cfg(test)]
+
+use model::U32;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_sdk::{json_types::U128, serde_json::json, test_utils::accounts, AccountId, PromiseOrValue};
+
+use crate::{
+    claim::api::ClaimApi,
+    common::{tests::Context, udecimal::UDecimal, MS_IN_YEAR},
+    compliance::api::ComplianceApi,
+    jar::{api::JarApi, model::JarTicket},
+    product::model::{Apy, Product},
+    withdraw::api::WithdrawApi,
+};
+
+fn prepare_context() -> (AccountId, AccountId, AccountId, Product, Context) {
+    let alice = accounts(0);
+    let bob = accounts(1);
+    let admin = accounts(2);
+
+    let product = Product::generate("product")
+        .enabled(true)
+        .lockup_term(MS_IN_YEAR)
+        .apy(Apy::Constant(UDecimal::new(12, 2)))
+        .cap(0, 1_000_000);
+    let context = Context::new(admin.clone())
+        .with_products(&[product.clone()])
+        .with_storage_deposit(&[alice.clone(), bob.clone()]);
+
+    (alice, bob, admin, product, context)
+}
+
+fn create_jar(context: &mut Context, account_id: &AccountId, product: &Product, amount: u128) -> U32 {
+    let ticket = JarTicket {
+        product_id: product.id.clone(),
+        valid_until: 0.into(),
+    };
+
+    context
+        .contract
+        .create_jar(account_id.clone(), ticket, U128(amount), None)
+        .id
+}
+
+#[test]
+fn block_and_unblock_accounts() {
+    let (alice, bob, admin, _, mut context) = prepare_context();
+
+    context.switch_account(&admin);
+    context
+        .contract
+        .batch_set_blocked(vec![alice.clone(), bob.clone()], true);
+
+    assert!(context.contract.is_blocked(alice.clone()));
+    assert!(context.contract.is_blocked(bob.clone()));
+
+    context.contract.set_blocked(bob.clone(), false);
+
+    assert!(context.contract.is_blocked(alice));
+    assert!(!context.contract.is_blocked(bob));
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn block_account_by_not_admin() {
+    let (alice, bob, _, _, mut context) = prepare_context();
+
+    context.switch_account(&alice);
+    context.contract.set_blocked(bob, true);
+}
+
+#[test]
+#[should_panic(expected = "Account 'bob' is blocked")]
+fn stake_for_blocked_receiver() {
+    let (alice, bob, admin, product, mut context) = prepare_context();
+
+    context.switch_account(&admin);
+    context.contract.set_blocked(bob.clone(), true);
+
+    let msg = json!({
+        "type": "stake",
+        "data": {
+            "ticket": {
+                "product_id": product.id,
+                "valid_until": "0",
+            },
+            "receiver_id": bob,
+        }
+    });
+
+    context.switch_account_to_ft_contract_account();
+    context.contract.ft_on_transfer(alice, U128(100), msg.to_string());
+}
+
+#[test]
+#[should_panic(expected = "Account 'alice' is blocked")]
+fn withdraw_by_blocked_account() {
+    let (alice, _, admin, product, mut context) = prepare_context();
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100);
+
+    context.switch_account(&admin);
+    context.contract.set_blocked(alice.clone(), true);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+    context.switch_account(&alice);
+    context.contract.withdraw(jar_id, None, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Account 'alice' is blocked")]
+fn split_jar_by_blocked_account() {
+    let (alice, _, admin, product, mut context) = prepare_context();
+
+    let jar_id = create_jar(&mut context, &alice, &product, 1_000);
+
+    context.switch_account(&admin);
+    context.contract.set_blocked(alice.clone(), true);
+
+    context.switch_account(&alice);
+    context.contract.split_jar(jar_id, U128(100));
+}
+
+#[test]
+#[should_panic(expected = "Account 'alice' is blocked")]
+fn claim_by_blocked_account() {
+    let (alice, _, admin, product, mut context) = prepare_context();
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100);
+
+    context.switch_account(&admin);
+    context.contract.set_blocked(alice.clone(), true);
+
+    context.set_block_timestamp_in_days(10);
+    context.switch_account(&alice);
+    context.contract.claim_jars(vec![jar_id], None, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Jar with id: 1 is frozen")]
+fn withdraw_from_frozen_jar() {
+    let (alice, _, admin, product, mut context) = prepare_context();
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100);
+
+    context.switch_account(&admin);
+    context.contract.set_frozen(alice.clone(), jar_id, true, None);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+    context.switch_account(&alice);
+    context.contract.withdraw(jar_id, None, None, None, None);
+}
+
+#[test]
+fn frozen_jar_without_interest_accrual() {
+    let (alice, _, admin, product, mut context) = prepare_context();
+
+    let jar_id = create_jar(&mut context, &alice, &product, 1_000_000);
+
+    context.set_block_timestamp_in_days(100);
+    let interest_before_freeze = context.contract.get_total_interest(alice.clone()).amount.total.0;
+
+    context.switch_account(&admin);
+    context.contract.set_frozen(alice.clone(), jar_id, true, Some(false));
+
+    let freeze = context.contract.get_jar_freeze(alice.clone(), jar_id).unwrap();
+    assert!(!freeze.accrues_interest);
+
+    context.set_block_timestamp_in_days(200);
+    assert_eq!(
+        interest_before_freeze,
+        context.contract.get_total_interest(alice.clone()).amount.total.0
+    );
+
+    context.contract.set_frozen(alice.clone(), jar_id, false, None);
+    assert!(context.contract.get_jar_freeze(alice.clone(), jar_id).is_none());
+
+    context.set_block_timestamp_in_days(300);
+    let interest_after_unfreeze = context.contract.get_total_interest(alice).amount.total.0;
+    assert!(interest_after_unfreeze > interest_before_freeze);
+    assert!(interest_after_unfreeze < 2 * interest_before_freeze + 2);
+}
+
+#[test]
+fn batch_freeze_jars_with_interest_accrual() {
+    let (alice, bob, admin, product, mut context) = prepare_context();
+
+    let alice_jar_id = create_jar(&mut context, &alice, &product, 1_000_000);
+    let bob_jar_id = create_jar(&mut context, &bob, &product, 1_000_000);
+
+    context.switch_account(&admin);
+    context.contract.batch_set_frozen(
+        vec![(alice.clone(), vec![alice_jar_id]), (bob.clone(), vec![bob_jar_id])],
+        true,
+        None,
+    );
+
+    assert!(
+        context
+            .contract
+            .get_jar_freeze(alice.clone(), alice_jar_id)
+            .unwrap()
+            .accrues_interest
+    );
+    assert!(context.contract.get_jar_freeze(bob.clone(), bob_jar_id).is_some());
+
+    context.set_block_timestamp_in_days(100);
+    assert!(context.contract.get_total_interest(alice.clone()).amount.total.0 > 0);
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(claimed) = context.contract.claim_total() else {
+        panic!("Expected value");
+    };
+    assert_eq!(0, claimed.0);
+}
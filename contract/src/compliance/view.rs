@@ -0,0 +1,22 @@
+use near_sdk::{
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+};
+
+use crate::jar::model::JarFreeze;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarFreezeView {
+    pub frozen_at: U64,
+    pub accrues_interest: bool,
+}
+
+impl From<&JarFreeze> for JarFreezeView {
+    fn from(value: &JarFreeze) -> Self {
+        Self {
+            frozen_at: U64(value.frozen_at),
+            accrues_interest: value.accrues_interest,
+        }
+    }
+}
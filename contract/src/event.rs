@@ -1,13 +1,20 @@
 use model::{jar::JarId, ProductId};
 use near_sdk::{
-    json_types::{Base64VecU8, U128},
+    json_types::{Base64VecU8, U128, U64},
     log,
     serde::{Deserialize, Serialize},
     serde_json, AccountId,
 };
 
-use crate::common::Timestamp;
-use crate::{env, jar::model::Jar, product::model::Product, PACKAGE_NAME, VERSION};
+use crate::{
+    common::{Duration, Timestamp},
+    env,
+    jar::model::Jar,
+    operator::model::OperatorAction,
+    outflow::view::OutflowLimitView,
+    product::model::Product,
+    PACKAGE_NAME, VERSION,
+};
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(
@@ -28,6 +35,27 @@ pub enum EventKind {
     EnableProduct(EnableProductData),
     ChangeProductPublicKey(ChangeProductPublicKeyData),
     TopUp(TopUpData),
+    TransferJar(TransferJarData),
+    SplitJar(SplitJarData),
+    MergeJars(MergeJarsData),
+    ApproveOperator(ApproveOperatorData),
+    RevokeOperator(RevokeOperatorData),
+    ConsolidatedRestake(ConsolidatedRestakeData),
+    Exit(Vec<ExitEventItem>),
+    UnlockJars(UnlockJarsData),
+    SponsorStorage(SponsorStorageData),
+    CleanupAccount(CleanupAccountData),
+    SetHistoryCapacity(SetHistoryCapacityData),
+    BlockAccount(BlockAccountData),
+    BatchBlockAccounts(BatchBlockAccountsData),
+    FreezeJar(FreezeJarData),
+    BatchFreezeJars(BatchFreezeJarsData),
+    RequestWithdraw(RequestWithdrawData),
+    CancelWithdraw(CancelWithdrawData),
+    FinalizeWithdraw(WithdrawData),
+    SetOutflowLimit(SetOutflowLimitData),
+    SetMaxPenaltyLookback(SetMaxPenaltyLookbackData),
+    SetPenaltyKey(SetPenaltyKeyData),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -54,6 +82,15 @@ pub struct WithdrawData {
     pub withdrawn_amount: U128,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ExitEventItem {
+    pub id: JarId,
+    pub withdrawn_amount: U128,
+    pub fee_amount: U128,
+    pub interest_to_claim: U128,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct MigrationEventItem {
@@ -67,6 +104,21 @@ pub struct MigrationEventItem {
 pub struct RestakeData {
     pub old_id: JarId,
     pub new_id: JarId,
+    pub old_product_id: ProductId,
+    pub new_product_id: ProductId,
+    pub principal: U128,
+    pub rolled_interest: U128,
+    pub top_up_amount: U128,
+    pub withdrawn_amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ConsolidatedRestakeData {
+    pub old_ids: Vec<JarId>,
+    pub new_id: JarId,
+    pub product_id: ProductId,
+    pub principal: U128,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -106,6 +158,141 @@ pub struct TopUpData {
     pub amount: U128,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferJarData {
+    pub old_id: JarId,
+    pub new_id: JarId,
+    pub sender_id: AccountId,
+    pub receiver_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SplitJarData {
+    pub old_id: JarId,
+    pub new_id: JarId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MergeJarsData {
+    pub old_ids: Vec<JarId>,
+    pub new_id: JarId,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApproveOperatorData {
+    pub account_id: AccountId,
+    pub operator_id: AccountId,
+    pub actions: Option<Vec<OperatorAction>>,
+    pub expires_at: Option<U64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RevokeOperatorData {
+    pub account_id: AccountId,
+    pub operator_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UnlockJarsData {
+    pub account_id: AccountId,
+    pub ids: Vec<JarId>,
+    pub is_restored: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorStorageData {
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CleanupAccountData {
+    pub account_id: AccountId,
+    pub storage_refund: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SetHistoryCapacityData {
+    pub capacity: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SetMaxPenaltyLookbackData {
+    pub lookback: Duration,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SetPenaltyKeyData {
+    pub product_id: ProductId,
+    pub pk: Option<Base64VecU8>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RequestWithdrawData {
+    pub id: JarId,
+    pub amount: U128,
+    pub available_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CancelWithdrawData {
+    pub id: JarId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SetOutflowLimitData {
+    pub product_id: ProductId,
+    pub limit: Option<OutflowLimitView>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BlockAccountData {
+    pub account_id: AccountId,
+    pub is_blocked: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BatchBlockAccountsData {
+    pub account_ids: Vec<AccountId>,
+    pub is_blocked: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FreezeJarData {
+    pub id: JarId,
+    pub is_frozen: bool,
+    pub accrues_interest: bool,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BatchFreezeJarsData {
+    pub jars: Vec<JarId>,
+    pub is_frozen: bool,
+    pub accrues_interest: bool,
+    pub timestamp: Timestamp,
+}
+
 impl From<EventKind> for SweatJarEvent {
     fn from(event_kind: EventKind) -> Self {
         Self {
@@ -0,0 +1,281 @@
+use model::{exit::ExitView, jar::JarIdView, TokenAmount, U32};
+use near_sdk::{env, ext_contract, is_promise_success, json_types::U128, near_bindgen, AccountId, PromiseOrValue};
+
+use crate::{
+    common::Timestamp,
+    event::{emit, EventKind, ExitEventItem},
+    history::model::JarActivityKind,
+    jar::model::{Jar, LockKind},
+    Contract, ContractExt,
+};
+
+/// The `ExitApi` trait defines methods for withdrawing principal and claiming interest from several jars
+/// with a single token transfer.
+pub trait ExitApi {
+    /// Withdraws all withdrawable principal and claims all available interest from specific deposit jars.
+    ///
+    /// Principal is withdrawn only from jars which are liquidable at the moment, while interest is claimed
+    /// from all the specified jars. Withdrawal fees of the products are aggregated into a single fee transfer.
+    /// Jars with another operation in progress are skipped. Jars left without principal and interest are closed.
+    /// Withdrawn principal counts towards outflow limits of the products.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - A `Vec<JarIdView>` containing the IDs of the deposit jars to exit.
+    ///
+    /// # Returns
+    ///
+    /// A `PromiseOrValue<ExitView>` representing the withdrawn principal, fee and claimed interest.
+    /// If there is nothing to withdraw or claim, or the transfer fails, all the amounts are zero.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the caller is blocked or if the withdrawn principal exceeds
+    /// the outflow allowance of a product within the current window.
+    fn exit(&mut self, jar_ids: Vec<JarIdView>) -> PromiseOrValue<ExitView>;
+
+    /// Withdraws all withdrawable principal and claims all available interest from all deposit jars
+    /// belonging to the calling account. See `exit` for details.
+    fn exit_all(&mut self) -> PromiseOrValue<ExitView>;
+}
+
+#[ext_contract(ext_self)]
+pub trait ExitCallbacks {
+    fn after_exit(
+        &mut self,
+        account_id: AccountId,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event_data: Vec<ExitEventItem>,
+        now: Timestamp,
+    ) -> ExitView;
+}
+
+#[near_bindgen]
+impl ExitApi for Contract {
+    fn exit(&mut self, jar_ids: Vec<JarIdView>) -> PromiseOrValue<ExitView> {
+        let account_id = env::predecessor_account_id();
+        let now = env::block_timestamp_ms();
+
+        self.assert_not_blocked(&account_id);
+
+        let unlocked_jars: Vec<Jar> = self
+            .find_account_jars_with_ids(&account_id, &jar_ids)
+            .into_iter()
+            .filter(|jar| !jar.is_pending_withdraw && !jar.is_frozen())
+            .cloned()
+            .collect();
+
+        let mut jars_before_transfer: Vec<Jar> = vec![];
+        let mut event_data: Vec<ExitEventItem> = vec![];
+        let lock_id = self.increment_and_get_last_lock_id();
+
+        for jar in unlocked_jars {
+            let product = self.get_product(&jar.product_id);
+
+            let withdrawn_amount = if jar.is_liquidable(product, now) {
+                jar.principal
+            } else {
+                0
+            };
+            let fee_amount = if withdrawn_amount > 0 {
+                self.get_fee(product, &jar)
+                    .map_or(0, |fee| fee.amount.min(withdrawn_amount))
+            } else {
+                0
+            };
+
+            let mut exited_jar = jar.withdrawn(product, withdrawn_amount, now);
+            let interest = exited_jar.get_interest(product, now);
+
+            if withdrawn_amount == 0 && interest == 0 {
+                continue;
+            }
+
+            exited_jar.claim(interest, interest, now);
+            exited_jar.lock(lock_id, LockKind::Exit, &jar, withdrawn_amount, now);
+            *self.get_jar_mut_internal(&account_id, jar.id) = exited_jar;
+
+            self.consume_outflow(&jar.product_id, withdrawn_amount, now);
+            self.decrease_product_tvl(&jar.product_id, withdrawn_amount);
+
+            event_data.push(ExitEventItem {
+                id: jar.id,
+                withdrawn_amount: U128(withdrawn_amount - fee_amount),
+                fee_amount: U128(fee_amount),
+                interest_to_claim: U128(interest),
+            });
+            jars_before_transfer.push(jar);
+        }
+
+        if event_data.is_empty() {
+            return PromiseOrValue::Value(ExitView::empty());
+        }
+
+        self.transfer_exit(&account_id, lock_id, jars_before_transfer, event_data, now)
+    }
+
+    fn exit_all(&mut self) -> PromiseOrValue<ExitView> {
+        let account_id = env::predecessor_account_id();
+        let jar_ids = self.account_jar_ids(&account_id).into_iter().map(U32).collect();
+
+        self.exit(jar_ids)
+    }
+}
+
+impl Contract {
+    fn get_exit_amounts(event_data: &[ExitEventItem]) -> (TokenAmount, TokenAmount, TokenAmount) {
+        event_data.iter().fold((0, 0, 0), |(withdrawn, fee, claimed), item| {
+            (
+                withdrawn + item.withdrawn_amount.0,
+                fee + item.fee_amount.0,
+                claimed + item.interest_to_claim.0,
+            )
+        })
+    }
+
+    /// Completes an exit. Jars which aren't held by the lock of the exit anymore are left untouched.
+    fn after_exit_internal(
+        &mut self,
+        account_id: &AccountId,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event_data: Vec<ExitEventItem>,
+        now: Timestamp,
+        is_promise_success: bool,
+    ) -> ExitView {
+        let (jars_before_transfer, event_data): (Vec<Jar>, Vec<ExitEventItem>) = jars_before_transfer
+            .into_iter()
+            .zip(event_data)
+            .filter(|(jar, _)| self.is_locked_by(jar.id, lock_id))
+            .unzip();
+
+        if event_data.is_empty() {
+            return ExitView::empty();
+        }
+
+        if !is_promise_success {
+            for (jar_before_transfer, item) in jars_before_transfer.into_iter().zip(&event_data) {
+                let withdrawn_amount = item.withdrawn_amount.0 + item.fee_amount.0;
+
+                self.release_outflow(&jar_before_transfer.product_id, withdrawn_amount);
+                self.increase_product_tvl(&jar_before_transfer.product_id, withdrawn_amount);
+
+                *self.get_jar_mut_internal(account_id, jar_before_transfer.id) = jar_before_transfer.unlocked();
+            }
+
+            return ExitView::empty();
+        }
+
+        for item in &event_data {
+            let withdrawn_amount = item.withdrawn_amount.0 + item.fee_amount.0;
+
+            if withdrawn_amount > 0 {
+                self.record_activity(item.id, JarActivityKind::Withdraw, withdrawn_amount);
+            }
+
+            if item.interest_to_claim.0 > 0 {
+                self.record_activity(item.id, JarActivityKind::Claim, item.interest_to_claim.0);
+            }
+        }
+
+        for jar_before_transfer in jars_before_transfer {
+            self.get_jar_mut_internal(account_id, jar_before_transfer.id).unlock();
+
+            let jar = self.get_jar_internal(account_id, jar_before_transfer.id);
+
+            if jar.should_be_closed(self.get_product(&jar.product_id), now) {
+                self.close_jar(account_id, jar_before_transfer.id);
+            }
+        }
+
+        let (withdrawn_amount, fee, claimed_amount) = Self::get_exit_amounts(&event_data);
+
+        emit(EventKind::Exit(event_data));
+
+        ExitView {
+            withdrawn_amount: U128(withdrawn_amount),
+            fee: U128(fee),
+            claimed_amount: U128(claimed_amount),
+        }
+    }
+
+    #[cfg(test)]
+    fn transfer_exit(
+        &mut self,
+        account_id: &AccountId,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event_data: Vec<ExitEventItem>,
+        now: Timestamp,
+    ) -> PromiseOrValue<ExitView> {
+        PromiseOrValue::Value(self.after_exit_internal(
+            account_id,
+            lock_id,
+            jars_before_transfer,
+            event_data,
+            now,
+            crate::common::test_data::get_test_future_success(),
+        ))
+    }
+
+    #[cfg(not(test))]
+    #[mutants::skip] // Covered by integration tests
+    fn transfer_exit(
+        &mut self,
+        account_id: &AccountId,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event_data: Vec<ExitEventItem>,
+        now: Timestamp,
+    ) -> PromiseOrValue<ExitView> {
+        use model::withdraw::Fee;
+
+        use crate::ft_interface::FungibleTokenInterface;
+
+        let (withdrawn_amount, fee_amount, claimed_amount) = Self::get_exit_amounts(&event_data);
+        let fee = (fee_amount > 0).then(|| Fee {
+            beneficiary_id: self.fee_account_id.clone(),
+            amount: fee_amount,
+        });
+
+        let gas = crate::common::gas_data::gas_for_after_exit(jars_before_transfer.len());
+
+        self.ft_contract()
+            .transfer(
+                account_id,
+                withdrawn_amount + fee_amount + claimed_amount,
+                "exit",
+                &fee,
+                None,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(gas)
+                    .after_exit(account_id.clone(), lock_id, jars_before_transfer, event_data, now),
+            )
+            .into()
+    }
+}
+
+#[near_bindgen]
+impl ExitCallbacks for Contract {
+    #[private]
+    fn after_exit(
+        &mut self,
+        account_id: AccountId,
+        lock_id: u64,
+        jars_before_transfer: Vec<Jar>,
+        event_data: Vec<ExitEventItem>,
+        now: Timestamp,
+    ) -> ExitView {
+        self.after_exit_internal(
+            &account_id,
+            lock_id,
+            jars_before_transfer,
+            event_data,
+            now,
+            is_promise_success(),
+        )
+    }
+}
@@ -0,0 +1,120 @@
+#![cfg(test)]
+
+use model::{exit::ExitView, U32};
+use near_sdk::{json_types::U128, test_utils::accounts, PromiseOrValue};
+
+use crate::{
+    common::{test_data::set_test_future_success, tests::Context, udecimal::UDecimal, MS_IN_YEAR},
+    exit::api::ExitApi,
+    jar::{api::JarApi, model::Jar},
+    product::model::{Apy, Product, WithdrawalFee},
+};
+
+fn generate_product(id: &str, lockup_years: u64) -> Product {
+    Product::generate(id)
+        .enabled(true)
+        .apy(Apy::Constant(UDecimal::new(12, 2)))
+        .lockup_term(MS_IN_YEAR * lockup_years)
+}
+
+#[test]
+fn exit_all_withdraws_mature_principal_and_claims_interest() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let short_product = generate_product("short_product", 1);
+    let long_product = generate_product("long_product", 2);
+    let jars = [
+        Jar::generate(0, &alice, &short_product.id).principal(1_000_000),
+        Jar::generate(1, &alice, &long_product.id).principal(2_000_000),
+    ];
+    let mut context = Context::new(admin)
+        .with_products(&[short_product, long_product])
+        .with_jars(&jars);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    let interest = context.contract.get_total_interest(alice.clone()).amount.total;
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(result) = context.contract.exit_all() else {
+        panic!();
+    };
+
+    assert_eq!(
+        ExitView {
+            withdrawn_amount: U128(1_000_000),
+            fee: U128(0),
+            claimed_amount: interest,
+        },
+        result
+    );
+
+    let jars = context.contract.get_jars_for_account(alice);
+    assert_eq!(1, jars.len());
+    assert_eq!(1, jars[0].id.0);
+    assert_eq!(2_000_000, jars[0].principal.0);
+}
+
+#[test]
+fn exit_with_withdrawal_fee() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product("product", 1).with_withdrawal_fee(WithdrawalFee::Fix(100));
+    let jars = [
+        Jar::generate(0, &alice, &product.id).principal(1_000_000),
+        Jar::generate(1, &alice, &product.id).principal(1_000_000),
+    ];
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&jars);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(result) = context.contract.exit(vec![U32(0), U32(1)]) else {
+        panic!();
+    };
+
+    assert_eq!(U128(2_000_000 - 200), result.withdrawn_amount);
+    assert_eq!(U128(200), result.fee);
+    assert!(context.contract.get_jars_for_account(alice).is_empty());
+}
+
+#[test]
+fn exit_when_nothing_to_withdraw() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product("product", 1);
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar]);
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(result) = context.contract.exit_all() else {
+        panic!();
+    };
+
+    assert_eq!(ExitView::empty(), result);
+}
+
+#[test]
+fn failed_exit_transfer() {
+    set_test_future_success(false);
+
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_product("product", 1);
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(result) = context.contract.exit_all() else {
+        panic!();
+    };
+
+    assert_eq!(ExitView::empty(), result);
+    assert_eq!(&jar, context.contract.get_jar_internal(&alice, jar.id));
+}
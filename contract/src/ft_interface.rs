@@ -1,8 +1,11 @@
 use model::{withdraw::Fee, TokenAmount};
-use near_sdk::{near_bindgen, serde_json, serde_json::json, AccountId, Promise};
+use near_sdk::{env, near_bindgen, require, serde_json, serde_json::json, AccountId, Gas, Promise};
 
 use crate::{common::tgas, Contract, ContractExt};
 
+/// Gas attached to `ft_transfer_call`. It covers `ft_on_transfer` of the receiver and `ft_resolve_transfer`.
+const GAS_FOR_FT_TRANSFER_CALL: Gas = tgas(50);
+
 pub(crate) struct FungibleTokenContract {
     address: AccountId,
 }
@@ -22,24 +25,96 @@ impl Contract {
     }
 }
 
+impl Contract {
+    /// Returns the account which receives tokens paid out to `account_id`. Only the owner of the tokens
+    /// can send them to another account or attach a message for `ft_transfer_call`.
+    pub(crate) fn get_transfer_receiver(
+        &self,
+        account_id: &AccountId,
+        receiver_id: Option<AccountId>,
+        msg: &Option<String>,
+    ) -> AccountId {
+        let receiver_id = receiver_id.unwrap_or_else(|| account_id.clone());
+
+        if &receiver_id != account_id || msg.is_some() {
+            require!(
+                &env::predecessor_account_id() == account_id,
+                "Only the owner can choose the receiver"
+            );
+        }
+
+        self.assert_not_blocked(&receiver_id);
+
+        receiver_id
+    }
+}
+
 pub(crate) trait FungibleTokenInterface {
-    fn transfer(&self, receiver_id: &AccountId, amount: u128, memo: &str, fee: &Option<Fee>) -> Promise;
+    /// Transfers tokens to the receiver. If `msg` is provided, tokens are transferred with `ft_transfer_call`.
+    fn transfer(
+        &self,
+        receiver_id: &AccountId,
+        amount: u128,
+        memo: &str,
+        fee: &Option<Fee>,
+        msg: Option<String>,
+    ) -> Promise;
 }
 
 impl FungibleTokenInterface for FungibleTokenContract {
-    fn transfer(&self, receiver_id: &AccountId, amount: u128, memo: &str, fee: &Option<Fee>) -> Promise {
+    fn transfer(
+        &self,
+        receiver_id: &AccountId,
+        amount: u128,
+        memo: &str,
+        fee: &Option<Fee>,
+        msg: Option<String>,
+    ) -> Promise {
+        let mut promise = Promise::new(self.address.clone());
+        let mut amount = amount;
+
         if let Some(fee) = fee {
-            Promise::new(self.address.clone())
-                .ft_transfer(receiver_id, amount - fee.amount, Some(memo.to_string()))
-                .ft_transfer(&fee.beneficiary_id, fee.amount, Some(format!("{memo} fee")))
+            promise = promise.ft_transfer(&fee.beneficiary_id, fee.amount, Some(format!("{memo} fee")));
+            amount -= fee.amount;
+        }
+
+        // The transfer to the receiver goes last, so its result is the result of the whole batch
+        // and the callback can read the amount used by the receiver.
+        if let Some(msg) = msg {
+            promise.ft_transfer_call(receiver_id, amount, Some(memo.to_string()), msg)
         } else {
-            Promise::new(self.address.clone()).ft_transfer(receiver_id, amount, Some(memo.to_string()))
+            promise.ft_transfer(receiver_id, amount, Some(memo.to_string()))
         }
     }
 }
 
+/// Returns the part of `amount` which was refunded to the contract by `ft_resolve_transfer`
+/// after `ft_transfer_call`. Nothing is refunded after a plain `ft_transfer`.
+pub(crate) fn get_transfer_refund(amount: TokenAmount, is_transfer_call: bool) -> TokenAmount {
+    use near_sdk::{json_types::U128, PromiseResult};
+
+    if !is_transfer_call {
+        return 0;
+    }
+
+    match env::promise_result(0) {
+        PromiseResult::Successful(value) => {
+            serde_json::from_slice::<U128>(&value).map_or(0, |used_amount| amount.saturating_sub(used_amount.0))
+        }
+        _ => 0,
+    }
+}
+
 trait FtTransferPromise {
     fn ft_transfer(self, receiver_id: &AccountId, amount: TokenAmount, memo: Option<String>) -> Promise;
+
+    fn ft_transfer_call(
+        self,
+        receiver_id: &AccountId,
+        amount: TokenAmount,
+        memo: Option<String>,
+        msg: String,
+    ) -> Promise;
 }
 
 impl FtTransferPromise for Promise {
@@ -53,4 +128,22 @@ impl FtTransferPromise for Promise {
 
         self.function_call("ft_transfer".to_string(), args, 1, tgas(5))
     }
+
+    fn ft_transfer_call(
+        self,
+        receiver_id: &AccountId,
+        amount: TokenAmount,
+        memo: Option<String>,
+        msg: String,
+    ) -> Promise {
+        let args = serde_json::to_vec(&json!({
+            "receiver_id": receiver_id,
+            "amount": amount.to_string(),
+            "memo": memo.unwrap_or_default(),
+            "msg": msg,
+        }))
+        .expect("Failed to serialize arguments");
+
+        self.function_call("ft_transfer_call".to_string(), args, 1, GAS_FOR_FT_TRANSFER_CALL)
+    }
 }
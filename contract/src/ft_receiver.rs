@@ -24,6 +24,15 @@ pub enum FtMessage {
 
     /// Represents a request to refill (top up) an existing jar using its `JarId`.
     TopUp(JarId),
+
+    /// Represents a request to restake a mature jar adding the transferred tokens to its principal.
+    Restake {
+        jar_id: JarId,
+
+        /// Whether the interest accrued by the jar is added to the principal of the new jar.
+        #[serde(default)]
+        with_interest: bool,
+    },
 }
 
 /// The `StakeMessage` struct represents a request to create a new jar for a corresponding product.
@@ -49,7 +58,11 @@ impl FungibleTokenReceiver for Contract {
 
         match ft_message {
             FtMessage::Stake(message) => {
+                self.assert_not_blocked(&sender_id);
+
                 let receiver_id = message.receiver_id.unwrap_or(sender_id);
+                self.assert_not_blocked(&receiver_id);
+
                 self.create_jar(receiver_id, message.ticket, amount, message.signature);
             }
             FtMessage::Migrate(jars) => {
@@ -58,8 +71,12 @@ impl FungibleTokenReceiver for Contract {
                 self.migrate_jars(jars, amount);
             }
             FtMessage::TopUp(jar_id) => {
+                self.assert_not_blocked(&sender_id);
                 self.top_up(&sender_id, jar_id, amount);
             }
+            FtMessage::Restake { jar_id, with_interest } => {
+                self.restake_with_top_up(&sender_id, jar_id, amount.0, with_interest);
+            }
         }
 
         PromiseOrValue::Value(0.into())
@@ -75,7 +92,7 @@ mod tests {
     use near_sdk::{json_types::U128, serde_json::json, test_utils::accounts};
 
     use crate::{
-        common::{tests::Context, udecimal::UDecimal},
+        common::{tests::Context, udecimal::UDecimal, MS_IN_YEAR},
         jar::{api::JarApi, model::Jar},
         product::{
             helpers::MessageSigner,
@@ -90,7 +107,9 @@ mod tests {
         let admin = accounts(1);
 
         let reference_product = Product::generate("test_product").enabled(true);
-        let mut context = Context::new(admin).with_products(&[reference_product.clone()]);
+        let mut context = Context::new(admin)
+            .with_products(&[reference_product.clone()])
+            .with_storage_deposit(&[alice.clone()]);
 
         let msg = json!({
             "type": "stake",
@@ -118,7 +137,9 @@ mod tests {
 
         let (signer, reference_product) = generate_premium_product_context();
 
-        let mut context = Context::new(admin).with_products(&[reference_product.clone()]);
+        let mut context = Context::new(admin)
+            .with_products(&[reference_product.clone()])
+            .with_storage_deposit(&[alice.clone()]);
 
         let ticket_amount = 1_000_000u128;
         let ticket_valid_until = 100_000_000u64;
@@ -176,7 +197,8 @@ mod tests {
 
         let mut context = Context::new(admin)
             .with_products(&[reference_product])
-            .with_jars(&[reference_jar.clone()]);
+            .with_jars(&[reference_jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
 
         let msg = json!({
             "type": "top_up",
@@ -193,6 +215,45 @@ mod tests {
         assert_eq!(initial_jar_principal + top_up_amount, jar.principal.0);
     }
 
+    #[test]
+    fn transfer_with_restake_message() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let reference_product = Product::generate("restakable_product")
+            .enabled(true)
+            .apy(Apy::Constant(UDecimal::new(12, 2)))
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true)
+            .cap(0, 10_000_000);
+
+        let reference_jar = Jar::generate(0, &alice, &reference_product.id).principal(1_000_000);
+
+        let mut context = Context::new(admin)
+            .with_products(&[reference_product])
+            .with_jars(&[reference_jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        let msg = json!({
+            "type": "restake",
+            "data": {
+                "jar_id": reference_jar.id,
+                "with_interest": true,
+            },
+        });
+
+        context.switch_account_to_ft_contract_account();
+        context
+            .contract
+            .ft_on_transfer(alice.clone(), U128(500_000), msg.to_string());
+
+        let jars = context.contract.get_jars_for_account(alice);
+        assert_eq!(1, jars.len());
+        assert_eq!(1_000_000 + 500_000 + 120_000, jars[0].principal.0);
+    }
+
     #[test]
     #[should_panic(expected = "The product doesn't allow top-ups")]
     fn transfer_with_top_up_message_for_not_refillable_product() {
@@ -234,7 +295,8 @@ mod tests {
 
         let mut context = Context::new(admin)
             .with_products(&[reference_product])
-            .with_jars(&[reference_jar.clone()]);
+            .with_jars(&[reference_jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
 
         let msg = json!({
             "type": "top_up",
@@ -262,7 +324,8 @@ mod tests {
         let reference_restakable_product = Product::generate("restakable_product").enabled(true).cap(0, 1_000_000);
 
         let mut context = Context::new(admin.clone())
-            .with_products(&[reference_product.clone(), reference_restakable_product.clone()]);
+            .with_products(&[reference_product.clone(), reference_restakable_product.clone()])
+            .with_storage_deposit(&[alice.clone(), bob.clone()]);
 
         let amount_alice = 100;
         let amount_bob = 200;
@@ -295,9 +358,12 @@ mod tests {
         assert_eq!(alice_jars.len(), 1);
         assert_eq!(alice_jars.first().unwrap().principal.0, amount_alice);
 
-        let bob_jars = context.contract.get_jars_for_account(bob);
+        let bob_jars = context.contract.get_jars_for_account(bob.clone());
         assert_eq!(bob_jars.len(), 1);
         assert_eq!(bob_jars.first().unwrap().principal.0, amount_bob);
+
+        let bob_jar_id = bob_jars.first().unwrap().id;
+        assert_eq!(Some(bob), context.contract.get_jar_owner(bob_jar_id));
     }
 
     #[test]
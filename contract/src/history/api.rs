@@ -0,0 +1,136 @@
+use model::{
+    jar::{JarId, JarIdView},
+    TokenAmount, U32,
+};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require};
+
+use crate::{
+    event::{emit, EventKind, SetHistoryCapacityData},
+    history::{
+        model::{JarActivity, JarActivityKind},
+        view::{JarActivityView, JarHistoryView},
+    },
+    jar::api::MAX_PAGE_LIMIT,
+    Contract, ContractExt,
+};
+
+/// The maximum number of activities stored for a single jar.
+pub const MAX_HISTORY_CAPACITY: u32 = 100;
+
+/// The `HistoryApi` trait defines methods for recording and retrieving activities of deposit jars.
+pub trait HistoryApi {
+    /// Sets the number of the latest activities stored for each jar. Zero disables recording,
+    /// while activities recorded before are kept until their jars are closed.
+    /// Storage used by the history is charged to the storage balance of the jar owner, even if it's not covered.
+    /// This method requires an attached deposit of exactly 1 yoctoNEAR.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of activities stored for each jar. It can't exceed `MAX_HISTORY_CAPACITY`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager
+    /// or if the capacity exceeds `MAX_HISTORY_CAPACITY`.
+    fn set_history_capacity(&mut self, capacity: u32);
+
+    /// Returns the number of the latest activities stored for each jar.
+    fn get_history_capacity(&self) -> u32;
+
+    /// Retrieves a page of activities of a deposit jar, from the oldest to the newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar.
+    /// * `from_index` - An optional index of the first activity of the page, e.g. `next_index` of the previous page.
+    ///                  Activities which are no longer stored are skipped.
+    /// * `limit` - An optional maximum number of activities on the page. It can't exceed `MAX_PAGE_LIMIT`.
+    ///
+    /// # Returns
+    ///
+    /// A `JarHistoryView` containing the activities of the page and the index of the first activity of the next page.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the jar doesn't exist or if the limit is zero.
+    fn get_jar_history(&self, jar_id: JarIdView, from_index: Option<U32>, limit: Option<u32>) -> JarHistoryView;
+}
+
+#[near_bindgen]
+impl HistoryApi for Contract {
+    #[payable]
+    fn set_history_capacity(&mut self, capacity: u32) {
+        self.assert_manager();
+        assert_one_yocto();
+
+        require!(
+            capacity <= MAX_HISTORY_CAPACITY,
+            format!("History capacity can't exceed {MAX_HISTORY_CAPACITY}")
+        );
+
+        self.history_capacity = capacity;
+
+        emit(EventKind::SetHistoryCapacity(SetHistoryCapacityData { capacity }));
+    }
+
+    fn get_history_capacity(&self) -> u32 {
+        self.history_capacity
+    }
+
+    fn get_jar_history(&self, jar_id: JarIdView, from_index: Option<U32>, limit: Option<u32>) -> JarHistoryView {
+        let jar_id = jar_id.0;
+        let limit = limit.map_or(MAX_PAGE_LIMIT, |limit| limit.min(MAX_PAGE_LIMIT));
+
+        require!(
+            self.jars.contains_key(&jar_id),
+            format!("Jar with id: {jar_id} doesn't exist")
+        );
+        require!(limit > 0, "Page limit must be positive");
+
+        let Some(history) = self.jar_history.get(&jar_id) else {
+            return JarHistoryView {
+                activities: vec![],
+                next_index: None,
+            };
+        };
+
+        let first_index = history.first_index();
+        let from_index = from_index.map_or(first_index, |index| index.0.max(first_index));
+        let to_index = from_index.saturating_add(limit).min(history.total);
+
+        let activities = (from_index..to_index)
+            .map(|index| JarActivityView::new(index, &history.activities[(index - first_index) as usize]))
+            .collect();
+
+        JarHistoryView {
+            activities,
+            next_index: (to_index < history.total).then_some(U32(to_index)),
+        }
+    }
+}
+
+impl Contract {
+    /// Records an activity of a jar if the history is enabled.
+    pub(crate) fn record_activity(&mut self, jar_id: JarId, kind: JarActivityKind, amount: TokenAmount) {
+        if self.history_capacity == 0 {
+            return;
+        }
+
+        let activity = JarActivity {
+            kind,
+            timestamp: env::block_timestamp_ms(),
+            amount,
+        };
+
+        let initial_storage_usage = self.flush_storage_usage();
+
+        self.jar_history
+            .entry(jar_id)
+            .or_default()
+            .record(activity, self.history_capacity);
+
+        if let Some(account_id) = self.jars.get(&jar_id).map(|jar| jar.account_id.clone()) {
+            self.charge_storage_unchecked(&account_id, initial_storage_usage);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod api;
+pub mod model;
+mod tests;
+pub mod view;
@@ -0,0 +1,75 @@
+use model::TokenAmount;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::common::Timestamp;
+
+/// The `JarActivityKind` enum describes state transitions of a jar recorded in its history.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum JarActivityKind {
+    /// The jar was created with the initial principal.
+    Create,
+
+    /// Tokens were added to the principal of the jar.
+    TopUp,
+
+    /// Interest was claimed from the jar.
+    Claim,
+
+    /// Principal was withdrawn from the jar.
+    Withdraw,
+
+    /// The penalty was applied to the jar, so it yields interest according to the fallback APY.
+    ApplyPenalty,
+
+    /// The penalty was cancelled, so the jar yields interest according to the default APY again.
+    CancelPenalty,
+
+    /// Principal was moved between the jar and another jar by restaking.
+    Restake,
+}
+
+/// The `JarActivity` struct describes a single state transition of a jar.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct JarActivity {
+    pub kind: JarActivityKind,
+
+    /// The timestamp of the transition, measured in milliseconds since Unix epoch.
+    pub timestamp: Timestamp,
+
+    /// The amount of tokens involved in the transition. It's zero for penalty changes.
+    pub amount: TokenAmount,
+}
+
+/// The `JarHistory` struct is a bounded list of the latest activities of a jar.
+/// When the list is full, the oldest activity is dropped to make room for a new one.
+#[derive(Default, BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+pub struct JarHistory {
+    /// The number of activities ever recorded for the jar. It's used to index activities,
+    /// so an index of an activity doesn't change when older activities are dropped.
+    pub total: u32,
+
+    /// The latest activities, from the oldest to the newest.
+    pub activities: Vec<JarActivity>,
+}
+
+impl JarHistory {
+    pub(crate) fn record(&mut self, activity: JarActivity, capacity: u32) {
+        let capacity = capacity as usize;
+
+        if self.activities.len() >= capacity {
+            self.activities.drain(..=self.activities.len() - capacity);
+        }
+
+        self.activities.push(activity);
+        self.total += 1;
+    }
+
+    /// Returns the index of the oldest activity which is still stored.
+    pub(crate) fn first_index(&self) -> u32 {
+        self.total - self.activities.len() as u32
+    }
+}
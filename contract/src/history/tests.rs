@@ -0,0 +1,162 @@
+#![cfg(test)]
+
+use model::U32;
+use near_sdk::{json_types::U128, test_utils::accounts, AccountId};
+
+use crate::{
+    claim::api::ClaimApi,
+    common::{test_data::set_test_future_refund, tests::Context, MS_IN_YEAR},
+    history::{
+        api::{HistoryApi, MAX_HISTORY_CAPACITY},
+        model::JarActivityKind,
+    },
+    jar::model::JarTicket,
+    product::model::Product,
+    withdraw::api::WithdrawApi,
+};
+
+fn prepare_context(capacity: u32) -> (AccountId, AccountId, Product, Context) {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("product")
+        .enabled(true)
+        .with_allows_top_up(true)
+        .lockup_term(MS_IN_YEAR)
+        .cap(0, 1_000_000);
+    let mut context = Context::new(admin.clone())
+        .with_products(&[product.clone()])
+        .with_storage_deposit(&[alice.clone()]);
+
+    if capacity > 0 {
+        context.switch_account(&admin);
+        context.with_deposit_yocto(1, |context| context.contract.set_history_capacity(capacity));
+    }
+
+    (alice, admin, product, context)
+}
+
+fn create_jar(context: &mut Context, account_id: &AccountId, product: &Product, amount: u128) -> U32 {
+    let ticket = JarTicket {
+        product_id: product.id.clone(),
+        valid_until: 0.into(),
+    };
+
+    context
+        .contract
+        .create_jar(account_id.clone(), ticket, U128(amount), None)
+        .id
+}
+
+#[test]
+fn history_is_disabled_by_default() {
+    let (alice, _, product, mut context) = prepare_context(0);
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100);
+
+    assert_eq!(0, context.contract.get_history_capacity());
+    assert!(context
+        .contract
+        .get_jar_history(jar_id, None, None)
+        .activities
+        .is_empty());
+}
+
+#[test]
+fn record_jar_activities() {
+    let (alice, _, product, mut context) = prepare_context(10);
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100);
+    context.contract.top_up(&alice, jar_id.0, U128(50));
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+    context.switch_account(&alice);
+    context.contract.withdraw(jar_id, Some(U128(30)), None, None, None);
+
+    let history = context.contract.get_jar_history(jar_id, None, None);
+    let activities: Vec<_> = history
+        .activities
+        .iter()
+        .map(|activity| (activity.index.0, activity.kind, activity.amount.0))
+        .collect();
+
+    assert_eq!(
+        vec![
+            (0, JarActivityKind::Create, 100),
+            (1, JarActivityKind::TopUp, 50),
+            (2, JarActivityKind::Withdraw, 30),
+        ],
+        activities
+    );
+    assert_eq!(MS_IN_YEAR + 1, history.activities[2].timestamp.0);
+    assert_eq!(None, history.next_index);
+}
+
+#[test]
+fn dont_record_fully_refunded_claim() {
+    let (alice, _, product, mut context) = prepare_context(10);
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100_000);
+
+    set_test_future_refund(1);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+    context.switch_account(&alice);
+    context.contract.claim_jars(
+        vec![jar_id],
+        Some(U128(1)),
+        None,
+        Some(accounts(3)),
+        Some("swap".to_string()),
+    );
+
+    let kinds: Vec<_> = context
+        .contract
+        .get_jar_history(jar_id, None, None)
+        .activities
+        .iter()
+        .map(|activity| activity.kind)
+        .collect();
+
+    assert_eq!(vec![JarActivityKind::Create], kinds);
+}
+
+#[test]
+fn drop_oldest_activities_and_paginate() {
+    let (alice, _, product, mut context) = prepare_context(2);
+
+    let jar_id = create_jar(&mut context, &alice, &product, 100);
+    context.contract.top_up(&alice, jar_id.0, U128(10));
+    context.contract.top_up(&alice, jar_id.0, U128(20));
+
+    let page = context.contract.get_jar_history(jar_id, Some(U32(0)), Some(1));
+    assert_eq!(1, page.activities.len());
+    assert_eq!(U32(1), page.activities[0].index);
+    assert_eq!(10, page.activities[0].amount.0);
+    assert_eq!(Some(U32(2)), page.next_index);
+
+    let page = context.contract.get_jar_history(jar_id, page.next_index, Some(1));
+    assert_eq!(U32(2), page.activities[0].index);
+    assert_eq!(20, page.activities[0].amount.0);
+    assert_eq!(None, page.next_index);
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn set_history_capacity_by_not_admin() {
+    let (alice, _, _, mut context) = prepare_context(0);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.set_history_capacity(10));
+}
+
+#[test]
+#[should_panic(expected = "History capacity can't exceed 100")]
+fn set_too_big_history_capacity() {
+    let (_, admin, _, mut context) = prepare_context(0);
+
+    context.switch_account(&admin);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_history_capacity(MAX_HISTORY_CAPACITY + 1);
+    });
+}
@@ -0,0 +1,36 @@
+use model::U32;
+use near_sdk::{
+    json_types::{U128, U64},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::history::model::{JarActivity, JarActivityKind};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarActivityView {
+    pub index: U32,
+    pub kind: JarActivityKind,
+    pub timestamp: U64,
+    pub amount: U128,
+}
+
+impl JarActivityView {
+    pub(crate) fn new(index: u32, activity: &JarActivity) -> Self {
+        Self {
+            index: U32(index),
+            kind: activity.kind,
+            timestamp: U64(activity.timestamp),
+            amount: U128(activity.amount),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarHistoryView {
+    pub activities: Vec<JarActivityView>,
+
+    /// The index of the first activity of the next page, if there is one.
+    pub next_index: Option<U32>,
+}
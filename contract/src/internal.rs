@@ -1,12 +1,10 @@
-use std::collections::HashMap;
-
 use model::{
     jar::{JarId, JarIdView},
     ProductId,
 };
 use near_sdk::require;
 
-use crate::{env, jar::model::Jar, AccountId, Contract, Product};
+use crate::{assert::require_ok, env, jar::model::Jar, AccountId, AccountJars, Contract, Product};
 
 impl Contract {
     pub(crate) fn assert_manager(&self) {
@@ -28,10 +26,28 @@ impl Contract {
         self.last_jar_id
     }
 
+    pub(crate) fn increment_and_get_last_lock_id(&mut self) -> u64 {
+        self.last_lock_id += 1;
+        self.last_lock_id
+    }
+
+    /// Indicates whether a jar is still held by the specified lock. A callback of an operation must not change
+    /// a jar which has been unlocked or locked by another operation since then.
+    pub(crate) fn is_locked_by(&self, jar_id: JarId, lock_id: u64) -> bool {
+        self.jars
+            .get(&jar_id)
+            .and_then(|jar| jar.lock.as_ref())
+            .map_or(false, |lock| lock.id == lock_id)
+    }
+
     pub(crate) fn get_product(&self, product_id: &ProductId) -> &Product {
+        require_ok(self.find_product(product_id))
+    }
+
+    pub(crate) fn find_product(&self, product_id: &ProductId) -> Result<&Product, String> {
         self.products
             .get(product_id)
-            .unwrap_or_else(|| env::panic_str(&format!("Product '{product_id}' doesn't exist")))
+            .ok_or_else(|| format!("Product '{product_id}' doesn't exist"))
     }
 
     pub(crate) fn get_product_mut(&mut self, product_id: &ProductId) -> &mut Product {
@@ -40,26 +56,85 @@ impl Contract {
             .unwrap_or_else(|| env::panic_str(&format!("Product '{product_id}' doesn't exist")))
     }
 
-    pub(crate) fn account_jars(&self, account_id: &AccountId) -> &[Jar] {
-        self.account_jars.get(account_id).map_or(&[], |jars| jars.as_slice())
+    pub(crate) fn account_jars(&self, account_id: &AccountId) -> Vec<&Jar> {
+        self.account_jars.get(account_id).map_or_else(Vec::new, |account_jars| {
+            account_jars.jar_ids.iter().map(|id| self.get_stored_jar(*id)).collect()
+        })
     }
 
-    pub(crate) fn account_jars_with_ids(&self, account_id: &AccountId, ids: &[JarIdView]) -> Vec<&Jar> {
-        // iterates once over jars and once over ids
-        let jars: HashMap<JarId, &Jar> = self.account_jars(account_id).iter().map(|jar| (jar.id, jar)).collect();
+    /// Returns IDs of all jars of the account ordered by ID. Only the account index is loaded.
+    pub(crate) fn account_jar_ids(&self, account_id: &AccountId) -> Vec<JarId> {
+        self.account_jars
+            .get(account_id)
+            .map_or_else(Vec::new, |account_jars| account_jars.jar_ids.clone())
+    }
+
+    /// Returns jars of the account with the provided IDs ordered by ID. IDs of missing jars
+    /// or jars of other accounts are ignored. Only the requested jars are loaded.
+    pub(crate) fn find_account_jars_with_ids(&self, account_id: &AccountId, ids: &[JarIdView]) -> Vec<&Jar> {
+        let mut ids: Vec<JarId> = ids.iter().map(|id| id.0).collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        ids.into_iter()
+            .filter_map(|id| self.jars.get(&id))
+            .filter(|jar| &jar.account_id == account_id)
+            .collect()
+    }
 
+    pub(crate) fn account_jars_with_ids(&self, account_id: &AccountId, ids: &[JarIdView]) -> Vec<&Jar> {
         ids.iter()
             .map(|id| {
-                *jars
+                self.jars
                     .get(&id.0)
+                    .filter(|jar| &jar.account_id == account_id)
                     .unwrap_or_else(|| env::panic_str(&format!("Jar with id: '{}' doesn't exist", id.0)))
             })
             .collect()
     }
 
+    /// Adds a jar to the account and charges the account for its storage.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the account isn't registered for storage or its storage balance
+    /// doesn't cover the jar.
     pub(crate) fn add_new_jar(&mut self, account_id: &AccountId, jar: Jar) {
-        let jars = self.account_jars.entry(account_id.clone()).or_default();
-        jars.last_id = jar.id;
-        jars.push(jar);
+        let initial_storage_usage = self.flush_storage_usage();
+
+        let account_jars = self.account_jars_entry(account_id);
+        account_jars.last_id = jar.id;
+        account_jars.insert_jar_id(jar.id);
+
+        self.jars.insert(jar.id, jar);
+
+        self.charge_storage(account_id, initial_storage_usage);
+    }
+
+    /// Returns the index of jars of an account, creating it if needed.
+    /// The last jar ID of an account which was cleaned up is restored, so signed tickets can't be reused.
+    pub(crate) fn account_jars_entry(&mut self, account_id: &AccountId) -> &mut AccountJars {
+        let last_jar_ids = &mut self.last_jar_ids;
+
+        self.account_jars
+            .entry(account_id.clone())
+            .or_insert_with(|| AccountJars {
+                last_id: last_jar_ids.remove(account_id).unwrap_or_default(),
+                jar_ids: vec![],
+            })
+    }
+
+    pub(crate) fn get_last_jar_id(&self, account_id: &AccountId) -> Option<JarId> {
+        self.account_jars
+            .get(account_id)
+            .map(|account_jars| account_jars.last_id)
+            .or_else(|| self.last_jar_ids.get(account_id).copied())
+    }
+
+    /// Returns a jar listed in an account index. A missing jar means that the storage is inconsistent.
+    pub(crate) fn get_stored_jar(&self, id: JarId) -> &Jar {
+        self.jars
+            .get(&id)
+            .unwrap_or_else(|| env::panic_str(&format!("Jar with id: {id} is missing in the storage")))
     }
 }
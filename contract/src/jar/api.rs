@@ -1,20 +1,37 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use model::{
-    jar::{JarIdView, JarView},
-    TokenAmount, U32,
+    jar::{JarId, JarIdView, JarView},
+    ProductId, TokenAmount, U32,
+};
+use near_sdk::{
+    assert_one_yocto, env,
+    json_types::{U128, U64},
+    near_bindgen, require, AccountId, PromiseOrValue,
 };
-use near_sdk::{env, json_types::U128, near_bindgen, require, AccountId};
 
 use crate::{
-    event::{emit, EventKind, RestakeData},
+    assert::{
+        assert_is_restakable, assert_not_frozen, assert_not_locked, assert_not_unbonding, check_is_restakable,
+        check_not_frozen, check_not_locked, require_ok,
+    },
+    common::Timestamp,
+    event::{emit, ConsolidatedRestakeData, EventKind, MergeJarsData, RestakeData, SplitJarData, TransferJarData},
+    history::model::JarActivityKind,
     jar::{
-        model::Jar,
-        view::{AggregatedInterestView, AggregatedTokenAmountView},
+        model::{Jar, JarFilter, JarTicket, LockKind},
+        view::{
+            AggregatedInterestView, AggregatedTokenAmountView, BatchRestakeView, InterestPageView, JarDetailsView,
+            JarsPageView, SkippedJarView,
+        },
     },
-    Contract, ContractExt,
+    operator::model::OperatorAction,
+    Base64VecU8, Contract, ContractExt,
 };
 
+/// The maximum number of jars returned on a single page of paginated methods.
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
 /// The `JarApi` trait defines methods for managing deposit jars and their associated data within the smart contract.
 pub trait JarApi {
     /// Retrieves information about a specific deposit jar by its index.
@@ -39,6 +56,79 @@ pub trait JarApi {
     /// A `Vec<JarView>` containing details about all deposit jars belonging to the specified account.
     fn get_jars_for_account(&self, account_id: AccountId) -> Vec<JarView>;
 
+    /// Retrieves information about a specific deposit jar by its ID alone. Jar IDs are unique across
+    /// all accounts, so the owner of the jar doesn't need to be known in advance.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar for which information is being retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A `JarView` struct containing details about the specified deposit jar.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the jar doesn't exist.
+    fn get_jar_by_id(&self, jar_id: JarIdView) -> JarView;
+
+    /// Retrieves the owner of a specific deposit jar.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar.
+    ///
+    /// # Returns
+    ///
+    /// The `AccountId` of the jar owner, or `None` if the jar doesn't exist.
+    fn get_jar_owner(&self, jar_id: JarIdView) -> Option<AccountId>;
+
+    /// Retrieves a page of deposit jars associated with a given account. Jars are ordered by ID,
+    /// so pages stay consistent when jars are created or closed between calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the account for which jar information is being retrieved.
+    /// * `filter` - An optional `JarFilter` describing which jars to include.
+    /// * `from_index` - An optional ID of the first jar of the page, e.g. `next_index` of the previous page.
+    /// * `limit` - An optional maximum number of jars on the page. It can't exceed `MAX_PAGE_LIMIT`.
+    ///
+    /// # Returns
+    ///
+    /// A `JarsPageView` containing the jars of the page and the ID of the first jar of the next page.
+    fn get_jars_for_account_paged(
+        &self,
+        account_id: AccountId,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+    ) -> JarsPageView;
+
+    /// Retrieves detailed information about a specific deposit jar, including values derived
+    /// from the jar's product at the current moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jar owner.
+    /// * `jar_id` - The ID of the deposit jar for which information is being retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A `JarDetailsView` struct containing the jar, its maturity date, effective APY, accrued interest,
+    /// withdrawal availability and fee, and the terms of its product.
+    fn get_jar_details(&self, account_id: AccountId, jar_id: JarIdView) -> JarDetailsView;
+
+    /// Retrieves detailed information about all deposit jars associated with a given account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the account for which jar information is being retrieved.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<JarDetailsView>` containing details about all deposit jars belonging to the specified account.
+    fn get_jars_details_for_account(&self, account_id: AccountId) -> Vec<JarDetailsView>;
+
     /// Retrieves the total principal amount across all deposit jars for a provided account.
     ///
     /// # Arguments
@@ -77,6 +167,27 @@ pub trait JarApi {
     /// Returns 0 if the account has no associated jars.
     fn get_total_interest(&self, account_id: AccountId) -> AggregatedInterestView;
 
+    /// Retrieves the interest amount for a page of deposit jars associated with a given account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the account for which the interest is being retrieved.
+    /// * `filter` - An optional `JarFilter` describing which jars to include.
+    /// * `from_index` - An optional ID of the first jar of the page, e.g. `next_index` of the previous page.
+    /// * `limit` - An optional maximum number of jars on the page. It can't exceed `MAX_PAGE_LIMIT`.
+    ///
+    /// # Returns
+    ///
+    /// An `InterestPageView` containing the interest of the jars of the page and the ID of the first jar
+    /// of the next page.
+    fn get_total_interest_paged(
+        &self,
+        account_id: AccountId,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+    ) -> InterestPageView;
+
     /// Retrieves the interest amount for a specific set of deposit jars.
     ///
     /// # Arguments
@@ -90,11 +201,78 @@ pub trait JarApi {
     ///
     fn get_interest(&self, jar_ids: Vec<JarIdView>, account_id: AccountId) -> AggregatedInterestView;
 
+    /// Projects the total interest amount across all deposit jars for a provided account at a future date.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the account for which the interest is being projected.
+    /// * `timestamp` - The date in milliseconds at which the interest is projected.
+    ///
+    /// # Returns
+    ///
+    /// An `AggregatedInterestView` containing the interest of every jar of the account at the specified date.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the specified date is in the past.
+    fn get_total_interest_at(&self, account_id: AccountId, timestamp: U64) -> AggregatedInterestView;
+
+    /// Projects the interest amount for a specific set of deposit jars at a future date.
+    ///
+    /// The projection is calculated the same way as the interest a jar accrues, so it matches
+    /// the amount the contract will pay at the specified date, unless the jar or its product changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - A `Vec<JarIdView>` containing the IDs of the deposit jars for which the
+    ///               interest is being projected.
+    /// * `account_id` - The `AccountId` of the jars owner.
+    /// * `timestamp` - The date in milliseconds at which the interest is projected.
+    ///
+    /// # Returns
+    ///
+    /// An `AggregatedInterestView` containing the interest of the specified jars at the specified date.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the specified date is in the past.
+    fn get_interest_at(&self, jar_ids: Vec<JarIdView>, account_id: AccountId, timestamp: U64)
+        -> AggregatedInterestView;
+
+    /// Projects the total interest amount that all Fixed deposit jars of a provided account will have
+    /// accrued at their maturity.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the account for which the interest is being projected.
+    ///
+    /// # Returns
+    ///
+    /// An `AggregatedTokenAmountView` containing the interest of every Fixed jar of the account at its maturity.
+    /// Flexible jars never mature, so they are not included.
+    fn get_total_interest_at_maturity(&self, account_id: AccountId) -> AggregatedTokenAmountView;
+
+    /// Projects the interest amount that a specific set of Fixed deposit jars will have accrued at their maturity.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - A `Vec<JarIdView>` containing the IDs of the deposit jars for which the
+    ///               interest is being projected.
+    /// * `account_id` - The `AccountId` of the jars owner.
+    ///
+    /// # Returns
+    ///
+    /// An `AggregatedTokenAmountView` containing the interest of the specified jars at their maturity.
+    /// Flexible jars never mature, so they are not included.
+    fn get_interest_at_maturity(&self, jar_ids: Vec<JarIdView>, account_id: AccountId) -> AggregatedTokenAmountView;
+
     /// Restakes the contents of a specified deposit jar into a new jar.
     ///
     /// # Arguments
     ///
     /// * `jar_id` - The ID of the deposit jar from which the restaking is being initiated.
+    /// * `account_id` - An optional `AccountId` of the jar owner. It allows an approved operator to restake
+    ///                  on behalf of the owner. The new jar always belongs to the owner.
     ///
     /// # Returns
     ///
@@ -106,7 +284,175 @@ pub trait JarApi {
     /// - If the product of the original jar does not support restaking.
     /// - If the function is called by an account other than the owner of the original jar.
     /// - If the original jar is not yet mature.
-    fn restake(&mut self, jar_id: JarIdView) -> JarView;
+    /// - If `account_id` is provided and the caller isn't an operator of this account allowed to restake.
+    fn restake(&mut self, jar_id: JarIdView, account_id: Option<AccountId>) -> JarView;
+
+    /// Restakes the principal of a mature deposit jar into a new jar of another product.
+    ///
+    /// Unlike `restake`, the product of the original jar doesn't have to allow restaking or be enabled,
+    /// so it can be used to move deposits from retired products. The new jar is created under the same
+    /// rules as a jar created with `ft_transfer_call`: the ticket is verified against the public key
+    /// of the target product, and the principal must be within its bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar from which the restaking is being initiated.
+    /// * `ticket` - A `JarTicket` specifying the target product.
+    /// * `signature` - An optional ed25519 signature of the ticket. It's required if the target product
+    ///                 has a public key.
+    ///
+    /// # Returns
+    ///
+    /// A `JarView` containing details about the new jar created as a result of the restaking.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the function is called by an account other than the owner of the original jar.
+    /// - If the original jar is not yet mature or empty.
+    /// - If the target product is disabled or the principal is out of its bounds.
+    /// - If the signature is missing or doesn't match the ticket.
+    fn restake_into(&mut self, jar_id: JarIdView, ticket: JarTicket, signature: Option<Base64VecU8>) -> JarView;
+
+    /// Restakes a part of the principal of a mature deposit jar into a new jar, withdrawing the rest of it
+    /// to the owner. The accrued interest can be optionally included in the principal of the new jar.
+    ///
+    /// To restake more than the principal of the jar, transfer the missing tokens with `ft_transfer_call`
+    /// using the `restake` message instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar from which the restaking is being initiated.
+    /// * `amount` - The amount of the principal to move to the new jar.
+    /// * `with_interest` - Whether the interest accrued by the original jar is added to the principal
+    ///                     of the new jar.
+    ///
+    /// # Returns
+    ///
+    /// A `PromiseOrValue<JarView>` containing details about the new jar created as a result of the restaking.
+    /// If a part of the principal is withdrawn, the view is returned once the transfer is completed.
+    /// The new jar is kept even if the transfer fails, in that case the withdrawn part returns to the original jar.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the product of the original jar does not support restaking or is disabled.
+    /// - If the function is called by an account other than the owner of the original jar.
+    /// - If the original jar is not yet mature.
+    /// - If the amount exceeds the principal of the original jar.
+    /// - If the principal of the new jar is out of the product bounds.
+    fn restake_partial(&mut self, jar_id: JarIdView, amount: U128, with_interest: bool) -> PromiseOrValue<JarView>;
+
+    /// Transfers a deposit jar to another account.
+    ///
+    /// The jar keeps its principal, accrued interest and penalty status, but it's assigned a new ID
+    /// so that the receiver's last jar ID used as a nonce for tickets only moves forward.
+    /// This method requires an attached deposit of exactly 1 yoctoNEAR.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar to transfer.
+    /// * `receiver_id` - The `AccountId` of the new owner of the jar.
+    ///
+    /// # Returns
+    ///
+    /// A `JarView` containing details about the jar under its new ID.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the function is called by an account other than the owner of the jar.
+    /// - If the receiver is the owner of the jar.
+    /// - If the product of the jar doesn't allow transfers.
+    /// - If another operation on the jar is in progress.
+    /// - If the receiver isn't registered for storage or its storage balance doesn't cover the jar.
+    fn transfer_jar(&mut self, jar_id: JarIdView, receiver_id: AccountId) -> JarView;
+
+    /// Splits a deposit jar into two jars sharing its principal and accrued interest.
+    ///
+    /// The original jar keeps its ID and the rest of the principal, while the new jar receives
+    /// the specified amount of principal and a proportional part of the accrued interest.
+    /// Both jars keep the creation date, so the maturity date of Fixed jars doesn't change.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the deposit jar to split.
+    /// * `amount` - The amount of principal to move to the new jar.
+    ///
+    /// # Returns
+    ///
+    /// A `JarView` containing details about the new jar.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the function is called by an account other than the owner of the jar.
+    /// - If the caller is blocked.
+    /// - If the amount is zero or isn't less than the principal of the jar.
+    /// - If the principal of either of the resulting jars is out of the product bounds.
+    /// - If another operation on the jar is in progress or the jar is unbonding.
+    /// - If the storage balance of the caller doesn't cover the new jar.
+    fn split_jar(&mut self, jar_id: JarIdView, amount: U128) -> JarView;
+
+    /// Merges several deposit jars of the same product into a new jar.
+    ///
+    /// The new jar receives the sum of principals, accrued interest and claimed balances of the merged jars.
+    /// For Fixed products the creation date of the new jar is the average of creation dates of the merged jars
+    /// weighted by their principal. Mature Fixed jars can't be merged, they should be restaked or withdrawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - The IDs of the deposit jars to merge.
+    ///
+    /// # Returns
+    ///
+    /// A `JarView` containing details about the new jar.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If less than two distinct jars are provided.
+    /// - If the caller is blocked.
+    /// - If the function is called by an account other than the owner of the jars.
+    /// - If the jars belong to different products or have different penalty status.
+    /// - If any of the jars of a Fixed product is mature.
+    /// - If the total principal is out of the product bounds.
+    /// - If another operation on any of the jars is in progress.
+    fn merge_jars(&mut self, jar_ids: Vec<JarIdView>) -> JarView;
+
+    /// Restakes all mature jars of the calling account in a single call.
+    ///
+    /// Jars which can't be restaked are skipped, and the reasons are returned along with the new jars.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_ids` - An optional list of products. If provided, only jars of these products are restaked.
+    /// * `consolidate` - If set, the principal of restaked jars is moved into a single new jar per product.
+    ///                   Otherwise a new jar is created for every restaked jar.
+    ///
+    /// # Returns
+    ///
+    /// A `BatchRestakeView` containing the new jars and the jars that were skipped.
+    fn restake_all(&mut self, product_ids: Option<Vec<ProductId>>, consolidate: bool) -> BatchRestakeView;
+
+    /// Restakes specified jars of the calling account in a single call.
+    ///
+    /// Jars which can't be restaked are skipped, and the reasons are returned along with the new jars.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - The IDs of the deposit jars to restake.
+    /// * `consolidate` - If set, the principal of restaked jars is moved into a single new jar per product.
+    ///                   Otherwise a new jar is created for every restaked jar.
+    ///
+    /// # Returns
+    ///
+    /// A `BatchRestakeView` containing the new jars and the jars that were skipped.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the list contains duplicate IDs.
+    fn restake_jars(&mut self, jar_ids: Vec<JarIdView>, consolidate: bool) -> BatchRestakeView;
 }
 
 #[near_bindgen]
@@ -116,12 +462,53 @@ impl JarApi for Contract {
     }
 
     fn get_jars_for_account(&self, account_id: AccountId) -> Vec<JarView> {
-        self.account_jars(&account_id).iter().map(Into::into).collect()
+        self.account_jars(&account_id).into_iter().map(Into::into).collect()
+    }
+
+    fn get_jar_by_id(&self, jar_id: JarIdView) -> JarView {
+        self.jars
+            .get(&jar_id.0)
+            .unwrap_or_else(|| env::panic_str(&format!("Jar with id: {} doesn't exist", jar_id.0)))
+            .into()
+    }
+
+    fn get_jar_owner(&self, jar_id: JarIdView) -> Option<AccountId> {
+        self.jars.get(&jar_id.0).map(|jar| jar.account_id.clone())
+    }
+
+    fn get_jars_for_account_paged(
+        &self,
+        account_id: AccountId,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+    ) -> JarsPageView {
+        let (jars, next_index) = self.get_jars_page(&account_id, filter, from_index, limit, env::block_timestamp_ms());
+
+        JarsPageView {
+            jars: jars.into_iter().map(Into::into).collect(),
+            next_index,
+        }
+    }
+
+    fn get_jar_details(&self, account_id: AccountId, jar_id: JarIdView) -> JarDetailsView {
+        let now = env::block_timestamp_ms();
+
+        self.get_jar_details_internal(self.get_jar_internal(&account_id, jar_id.0), now)
+    }
+
+    fn get_jars_details_for_account(&self, account_id: AccountId) -> Vec<JarDetailsView> {
+        let now = env::block_timestamp_ms();
+
+        self.account_jars(&account_id)
+            .into_iter()
+            .map(|jar| self.get_jar_details_internal(jar, now))
+            .collect()
     }
 
     fn get_total_principal(&self, account_id: AccountId) -> AggregatedTokenAmountView {
         self.get_principal(
-            self.account_jars(&account_id).iter().map(|a| U32(a.id)).collect(),
+            self.account_jar_ids(&account_id).into_iter().map(U32).collect(),
             account_id,
         )
     }
@@ -146,18 +533,335 @@ impl JarApi for Contract {
 
     fn get_total_interest(&self, account_id: AccountId) -> AggregatedInterestView {
         self.get_interest(
-            self.account_jars(&account_id).iter().map(|a| U32(a.id)).collect(),
+            self.account_jar_ids(&account_id).into_iter().map(U32).collect(),
             account_id,
         )
     }
 
-    fn get_interest(&self, jar_ids: Vec<JarIdView>, account_id: AccountId) -> AggregatedInterestView {
+    fn get_total_interest_paged(
+        &self,
+        account_id: AccountId,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+    ) -> InterestPageView {
         let now = env::block_timestamp_ms();
+        let (jars, next_index) = self.get_jars_page(&account_id, filter, from_index, limit, now);
+        let jar_ids: Vec<JarIdView> = jars.iter().map(|jar| U32(jar.id)).collect();
+
+        InterestPageView {
+            interest: self.get_interest_at_internal(&account_id, &jar_ids, now),
+            next_index,
+        }
+    }
+
+    fn get_interest(&self, jar_ids: Vec<JarIdView>, account_id: AccountId) -> AggregatedInterestView {
+        self.get_interest_at_internal(&account_id, &jar_ids, env::block_timestamp_ms())
+    }
+
+    fn get_total_interest_at(&self, account_id: AccountId, timestamp: U64) -> AggregatedInterestView {
+        self.get_interest_at(
+            self.account_jar_ids(&account_id).into_iter().map(U32).collect(),
+            account_id,
+            timestamp,
+        )
+    }
+
+    fn get_interest_at(
+        &self,
+        jar_ids: Vec<JarIdView>,
+        account_id: AccountId,
+        timestamp: U64,
+    ) -> AggregatedInterestView {
+        require!(
+            timestamp.0 >= env::block_timestamp_ms(),
+            "Interest can't be projected to a date in the past"
+        );
 
+        self.get_interest_at_internal(&account_id, &jar_ids, timestamp.0)
+    }
+
+    fn get_total_interest_at_maturity(&self, account_id: AccountId) -> AggregatedTokenAmountView {
+        self.get_interest_at_maturity(
+            self.account_jar_ids(&account_id).into_iter().map(U32).collect(),
+            account_id,
+        )
+    }
+
+    fn get_interest_at_maturity(&self, jar_ids: Vec<JarIdView>, account_id: AccountId) -> AggregatedTokenAmountView {
         let mut detailed_amounts = HashMap::<JarIdView, U128>::new();
         let mut total_amount: TokenAmount = 0;
 
         for jar in self.account_jars_with_ids(&account_id, &jar_ids) {
+            let product = self.get_product(&jar.product_id);
+
+            let Some(lockup_term) = product.get_lockup_term() else {
+                continue;
+            };
+
+            let interest = jar.get_interest(product, jar.created_at + lockup_term);
+
+            detailed_amounts.insert(U32(jar.id), U128(interest));
+            total_amount += interest;
+        }
+
+        AggregatedTokenAmountView {
+            detailed: detailed_amounts,
+            total: U128(total_amount),
+        }
+    }
+
+    fn restake(&mut self, jar_id: JarIdView, account_id: Option<AccountId>) -> JarView {
+        let account_id = self.get_account_for_action(account_id, OperatorAction::Restake);
+        let product_id = self.get_restakable_jar(&account_id, jar_id.0).product_id.clone();
+
+        self.restake_whole_principal(&account_id, jar_id.0, product_id, 0, false)
+    }
+
+    fn restake_into(&mut self, jar_id: JarIdView, ticket: JarTicket, signature: Option<Base64VecU8>) -> JarView {
+        let account_id = env::predecessor_account_id();
+        let jar = self.get_jar_internal(&account_id, jar_id.0);
+
+        let now = env::block_timestamp_ms();
+        assert_is_restakable(jar, self.get_product(&jar.product_id), now);
+
+        let principal = jar.principal;
+        let target_product = self.get_product(&ticket.product_id);
+
+        target_product.assert_enabled();
+        self.verify(&account_id, principal, &ticket, signature);
+
+        self.restake_whole_principal(&account_id, jar_id.0, ticket.product_id, 0, false)
+    }
+
+    fn restake_partial(&mut self, jar_id: JarIdView, amount: U128, with_interest: bool) -> PromiseOrValue<JarView> {
+        let account_id = env::predecessor_account_id();
+        let jar = self.get_restakable_jar(&account_id, jar_id.0);
+        let product_id = jar.product_id.clone();
+
+        require!(
+            amount.0 <= jar.principal,
+            "To restake more than the jar principal, transfer the missing tokens with `ft_transfer_call`"
+        );
+
+        self.restake_internal(&account_id, jar_id.0, product_id, amount.0, 0, with_interest)
+    }
+
+    #[payable]
+    fn transfer_jar(&mut self, jar_id: JarIdView, receiver_id: AccountId) -> JarView {
+        assert_one_yocto();
+
+        let jar_id = jar_id.0;
+        let account_id = env::predecessor_account_id();
+
+        require!(account_id != receiver_id, "Can't transfer a jar to its owner");
+        self.assert_not_blocked(&account_id);
+        self.assert_not_blocked(&receiver_id);
+
+        let jar = self.get_jar_internal(&account_id, jar_id).clone();
+
+        assert_not_locked(&jar);
+        assert_not_frozen(&jar);
+        assert_not_unbonding(&jar);
+
+        let product = self.get_product(&jar.product_id);
+
+        require!(product.is_transferable, "The product doesn't allow transfers");
+
+        let new_id = self.increment_and_get_last_jar_id();
+        let transferred_jar = Jar {
+            id: new_id,
+            account_id: receiver_id.clone(),
+            ..jar
+        };
+
+        self.close_jar(&account_id, jar_id);
+        self.add_new_jar(&receiver_id, transferred_jar.clone());
+
+        emit(EventKind::TransferJar(TransferJarData {
+            old_id: jar_id,
+            new_id,
+            sender_id: account_id,
+            receiver_id,
+        }));
+
+        transferred_jar.into()
+    }
+
+    fn split_jar(&mut self, jar_id: JarIdView, amount: U128) -> JarView {
+        let jar_id = jar_id.0;
+        let amount = amount.0;
+        let account_id = env::predecessor_account_id();
+
+        self.assert_not_blocked(&account_id);
+
+        let jar = self.get_jar_internal(&account_id, jar_id).clone();
+
+        assert_not_locked(&jar);
+        assert_not_frozen(&jar);
+        assert_not_unbonding(&jar);
+        require!(
+            0 < amount && amount < jar.principal,
+            "Split amount must be positive and less than the jar principal"
+        );
+
+        let product = self.get_product(&jar.product_id).clone();
+
+        product.assert_cap(amount);
+        product.assert_cap(jar.principal - amount);
+
+        let now = env::block_timestamp_ms();
+        let new_id = self.increment_and_get_last_jar_id();
+
+        let (remaining_jar, new_jar) = jar.split(new_id, amount, &product, now);
+
+        *self.get_jar_mut_internal(&account_id, jar_id) = remaining_jar;
+        self.add_new_jar(&account_id, new_jar.clone());
+
+        emit(EventKind::SplitJar(SplitJarData {
+            old_id: jar_id,
+            new_id,
+            amount: U128(amount),
+        }));
+
+        new_jar.into()
+    }
+
+    fn merge_jars(&mut self, jar_ids: Vec<JarIdView>) -> JarView {
+        let account_id = env::predecessor_account_id();
+
+        self.assert_not_blocked(&account_id);
+
+        let unique_ids: HashSet<JarIdView> = jar_ids.iter().copied().collect();
+        require!(
+            jar_ids.len() > 1 && unique_ids.len() == jar_ids.len(),
+            "At least two distinct jars are required to merge"
+        );
+
+        let jars: Vec<Jar> = self
+            .account_jars_with_ids(&account_id, &jar_ids)
+            .into_iter()
+            .cloned()
+            .collect();
+
+        let first_jar = &jars[0];
+        let product = self.get_product(&first_jar.product_id).clone();
+        let now = env::block_timestamp_ms();
+
+        for jar in &jars {
+            assert_not_locked(jar);
+            assert_not_frozen(jar);
+            assert_not_unbonding(jar);
+            require!(
+                jar.product_id == first_jar.product_id,
+                "Only jars of the same product can be merged"
+            );
+            require!(
+                jar.is_penalty_applied == first_jar.is_penalty_applied,
+                "Jars with different penalty status can't be merged"
+            );
+            require!(
+                product.is_flexible() || !jar.is_liquidable(&product, now),
+                "Mature jars can't be merged, restake or withdraw them instead"
+            );
+        }
+
+        let new_id = self.increment_and_get_last_jar_id();
+
+        let new_jar = Jar::merged(new_id, &jars, &product, now);
+
+        product.assert_cap(new_jar.principal);
+
+        for jar in &jars {
+            self.delete_jar(&account_id, jar.id);
+        }
+        self.add_new_jar(&account_id, new_jar.clone());
+
+        emit(EventKind::MergeJars(MergeJarsData {
+            old_ids: jars.iter().map(|jar| jar.id).collect(),
+            new_id,
+        }));
+
+        new_jar.into()
+    }
+
+    fn restake_all(&mut self, product_ids: Option<Vec<ProductId>>, consolidate: bool) -> BatchRestakeView {
+        let account_id = env::predecessor_account_id();
+        let jar_ids = self
+            .account_jars(&account_id)
+            .into_iter()
+            .filter(|jar| product_ids.as_ref().map_or(true, |ids| ids.contains(&jar.product_id)))
+            .map(|jar| jar.id)
+            .collect();
+
+        self.restake_batch(&account_id, jar_ids, consolidate)
+    }
+
+    fn restake_jars(&mut self, jar_ids: Vec<JarIdView>, consolidate: bool) -> BatchRestakeView {
+        let account_id = env::predecessor_account_id();
+
+        let unique_ids: HashSet<JarIdView> = jar_ids.iter().copied().collect();
+        require!(unique_ids.len() == jar_ids.len(), "Jar IDs must be unique");
+
+        let jar_ids = jar_ids.into_iter().map(|id| id.0).collect();
+
+        self.restake_batch(&account_id, jar_ids, consolidate)
+    }
+}
+
+impl Contract {
+    /// Returns a page of jars of the account matching the filter ordered by ID,
+    /// and the ID of the first jar of the next page if there is one.
+    pub(crate) fn get_jars_page(
+        &self,
+        account_id: &AccountId,
+        filter: Option<JarFilter>,
+        from_index: Option<JarIdView>,
+        limit: Option<u32>,
+        now: Timestamp,
+    ) -> (Vec<&Jar>, Option<JarIdView>) {
+        let filter = filter.unwrap_or_default();
+        let from_index = from_index.map_or(0, |index| index.0);
+        let limit = limit.map_or(MAX_PAGE_LIMIT, |limit| limit.min(MAX_PAGE_LIMIT)) as usize;
+
+        require!(limit > 0, "Page limit must be positive");
+
+        let Some(account_jars) = self.account_jars.get(account_id) else {
+            return (vec![], None);
+        };
+
+        // Jars are loaded one by one starting from the cursor, so the cost of a page doesn't depend
+        // on the total number of jars of the account
+        let start = account_jars.jar_ids.partition_point(|id| *id < from_index);
+        let mut jars: Vec<&Jar> = vec![];
+
+        for id in &account_jars.jar_ids[start..] {
+            let jar = self.get_stored_jar(*id);
+
+            if !filter.matches(jar, self.get_product(&jar.product_id), now) {
+                continue;
+            }
+
+            if jars.len() == limit {
+                return (jars, Some(U32(*id)));
+            }
+
+            jars.push(jar);
+        }
+
+        (jars, None)
+    }
+
+    fn get_interest_at_internal(
+        &self,
+        account_id: &AccountId,
+        jar_ids: &[JarIdView],
+        now: Timestamp,
+    ) -> AggregatedInterestView {
+        let mut detailed_amounts = HashMap::<JarIdView, U128>::new();
+        let mut total_amount: TokenAmount = 0;
+
+        for jar in self.account_jars_with_ids(account_id, jar_ids) {
             let interest = jar.get_interest(self.get_product(&jar.product_id), now);
 
             detailed_amounts.insert(U32(jar.id), U128(interest));
@@ -173,50 +877,316 @@ impl JarApi for Contract {
         }
     }
 
-    fn restake(&mut self, jar_id: JarIdView) -> JarView {
-        let jar_id = jar_id.0;
-        let account_id = env::predecessor_account_id();
+    fn get_jar_details_internal(&self, jar: &Jar, now: Timestamp) -> JarDetailsView {
+        let product = self.get_product(&jar.product_id);
 
-        let restaked_jar_id = self.increment_and_get_last_jar_id();
+        JarDetailsView {
+            jar: jar.into(),
+            maturity_date: product
+                .get_lockup_term()
+                .map(|lockup_term| U64(jar.created_at + lockup_term)),
+            apy: jar.get_apy(product).to_f32(),
+            interest: U128(jar.get_interest(product, now)),
+            is_pending_withdraw: jar.is_pending_withdraw,
+            is_withdrawable: !jar.is_empty() && self.check_withdraw(&jar.account_id, jar.id, None, now).is_ok(),
+            withdrawal_fee: U128(self.get_fee(product, jar).map_or(0, |fee| fee.amount)),
+            terms: product.terms.clone().into(),
+        }
+    }
 
-        let jar = self.get_jar_internal(&account_id, jar_id);
+    /// Restakes a jar with extra tokens transferred with `ft_transfer_call`. The new jar receives
+    /// the whole principal of the original jar along with the transferred tokens.
+    pub(crate) fn restake_with_top_up(
+        &mut self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        top_up: TokenAmount,
+        with_interest: bool,
+    ) -> JarView {
+        let product_id = self.get_restakable_jar(account_id, jar_id).product_id.clone();
 
-        let product = self.get_product(&jar.product_id);
+        self.restake_whole_principal(account_id, jar_id, product_id, top_up, with_interest)
+    }
+
+    /// Returns a jar if it can be restaked into its own product.
+    fn get_restakable_jar(&self, account_id: &AccountId, jar_id: JarId) -> &Jar {
+        require_ok(self.find_restakable_jar(account_id, jar_id, env::block_timestamp_ms()))
+    }
+
+    /// Finds a jar and checks whether it can be restaked into its own product. Returns the reason if it can't.
+    pub(crate) fn find_restakable_jar(
+        &self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        now: Timestamp,
+    ) -> Result<&Jar, String> {
+        let jar = self.find_jar(account_id, jar_id)?;
+
+        self.check_restakable(jar, now)?;
+
+        Ok(jar)
+    }
+
+    /// Checks whether a jar can be restaked into its own product. Returns the reason if it can't.
+    fn check_restakable(&self, jar: &Jar, now: Timestamp) -> Result<(), String> {
+        self.check_not_blocked(&jar.account_id)?;
 
-        require!(product.allows_restaking(), "The product doesn't support restaking");
-        require!(product.is_enabled, "The product is disabled");
+        let product = self.find_product(&jar.product_id)?;
+
+        if !product.allows_restaking() {
+            return Err("The product doesn't support restaking".to_string());
+        }
+
+        if !product.is_enabled {
+            return Err("The product is disabled".to_string());
+        }
+
+        check_is_restakable(jar, product, now)?;
+        check_not_locked(jar)?;
+        check_not_frozen(jar)
+    }
+
+    /// Restakes eligible jars from the list and collects reasons for skipping the rest of them.
+    /// If `consolidate` is set, the principal of restaked jars is moved into a single new jar per product.
+    fn restake_batch(&mut self, account_id: &AccountId, jar_ids: Vec<JarId>, consolidate: bool) -> BatchRestakeView {
+        self.assert_not_blocked(account_id);
 
         let now = env::block_timestamp_ms();
-        require!(jar.is_liquidable(product, now), "The jar is not mature yet");
-        require!(!jar.is_empty(), "The jar is empty, nothing to restake");
 
-        let principal = jar.principal;
+        let mut skipped: Vec<SkippedJarView> = vec![];
+        let mut eligible: Vec<(ProductId, Vec<JarId>)> = vec![];
+
+        for jar_id in jar_ids {
+            let check = self
+                .find_jar(account_id, jar_id)
+                .and_then(|jar| self.check_restakable(jar, now).map(|()| jar));
+
+            let jar = match check {
+                Ok(jar) => jar,
+                Err(reason) => {
+                    skipped.push(SkippedJarView::new(jar_id, reason));
+                    continue;
+                }
+            };
+
+            let group = if consolidate {
+                eligible
+                    .iter_mut()
+                    .find(|(product_id, _)| product_id == &jar.product_id)
+            } else {
+                None
+            };
+
+            match group {
+                Some((_, jars)) => jars.push(jar.id),
+                None => eligible.push((jar.product_id.clone(), vec![jar.id])),
+            }
+        }
+
+        let mut restaked: Vec<JarView> = vec![];
+
+        for (product_id, jar_ids) in eligible {
+            // Jars are validated again, since the state could be changed by restaking previous jars
+            let mut jars: Vec<(JarId, TokenAmount)> = vec![];
+
+            for jar_id in jar_ids {
+                match self.find_restakable_jar(account_id, jar_id, now) {
+                    Ok(jar) => jars.push((jar.id, jar.principal)),
+                    Err(reason) => skipped.push(SkippedJarView::new(jar_id, reason)),
+                }
+            }
+
+            if jars.is_empty() {
+                continue;
+            }
+
+            let principal: TokenAmount = jars.iter().map(|(_, principal)| principal).sum();
+
+            if let Err(reason) = self.get_product(&product_id).check_cap(principal) {
+                skipped.extend(
+                    jars.iter()
+                        .map(|(jar_id, _)| SkippedJarView::new(*jar_id, reason.clone())),
+                );
+                continue;
+            }
+
+            let new_jar = match jars.as_slice() {
+                [(jar_id, _)] => self.restake_whole_principal(account_id, *jar_id, product_id, 0, false),
+                _ => self.restake_consolidated(account_id, product_id, &jars, now),
+            };
+
+            restaked.push(new_jar);
+        }
+
+        BatchRestakeView { restaked, skipped }
+    }
+
+    /// Moves the whole principal of several mature jars of the same product into a single new jar.
+    fn restake_consolidated(
+        &mut self,
+        account_id: &AccountId,
+        product_id: ProductId,
+        jars: &[(JarId, TokenAmount)],
+        now: Timestamp,
+    ) -> JarView {
+        let product = self.get_product(&product_id).clone();
 
+        for (jar_id, principal) in jars {
+            let withdrawn_jar = self
+                .get_jar_internal(account_id, *jar_id)
+                .withdrawn(&product, *principal, now);
+
+            if withdrawn_jar.should_be_closed(&product, now) {
+                self.delete_jar(account_id, *jar_id);
+            } else {
+                *self.get_jar_mut_internal(account_id, *jar_id) = withdrawn_jar;
+                self.record_activity(*jar_id, JarActivityKind::Restake, *principal);
+            }
+        }
+
+        let principal = jars.iter().map(|(_, principal)| principal).sum();
         let new_jar = Jar::create(
-            restaked_jar_id,
-            jar.account_id.clone(),
-            jar.product_id.clone(),
+            self.increment_and_get_last_jar_id(),
+            account_id.clone(),
+            product_id,
             principal,
             now,
         );
 
-        let withdraw_jar = jar.withdrawn(product, principal, now);
-        let should_be_closed = withdraw_jar.should_be_closed(product, now);
+        self.add_new_jar(account_id, new_jar.clone());
+        self.record_activity(new_jar.id, JarActivityKind::Restake, principal);
+
+        emit(EventKind::ConsolidatedRestake(ConsolidatedRestakeData {
+            old_ids: jars.iter().map(|(jar_id, _)| *jar_id).collect(),
+            new_id: new_jar.id,
+            product_id: new_jar.product_id.clone(),
+            principal: U128(principal),
+        }));
+
+        new_jar.into()
+    }
+
+    /// Moves the principal of a mature jar into a new jar of the specified product.
+    ///
+    /// The new jar receives `amount` of the original principal, `top_up` tokens transferred along with
+    /// the request and, if `with_interest` is set, the interest accrued by the original jar. Otherwise the interest
+    /// stays in the original jar until it's claimed. The rest of the original principal is withdrawn to the owner.
+    fn restake_internal(
+        &mut self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        product_id: ProductId,
+        amount: TokenAmount,
+        top_up: TokenAmount,
+        with_interest: bool,
+    ) -> PromiseOrValue<JarView> {
+        let now = env::block_timestamp_ms();
+        let jar = self.get_jar_internal(account_id, jar_id).clone();
+
+        self.assert_not_blocked(account_id);
+        assert_not_locked(&jar);
+        assert_not_frozen(&jar);
+
+        let product = self.get_product(&jar.product_id);
+        let Some(withdrawn_amount) = jar.principal.checked_sub(amount) else {
+            env::panic_str("Restaked amount exceeds the jar principal");
+        };
+        let mut withdrawn_jar = jar.withdrawn(product, jar.principal, now);
 
-        if should_be_closed {
-            self.delete_jar(&withdraw_jar.account_id, withdraw_jar.id);
+        let rolled_interest = if with_interest {
+            let interest = withdrawn_jar.get_interest(product, now);
+            withdrawn_jar.claim(interest, interest, now);
+            interest
         } else {
-            let jar_id = withdraw_jar.id;
-            *self.get_jar_mut_internal(&account_id, jar_id) = withdraw_jar;
+            0
+        };
+
+        let principal = amount + top_up + rolled_interest;
+        require!(principal > 0, "Nothing to restake");
+        self.get_product(&product_id).assert_cap(principal);
+
+        let close_jar = withdrawn_jar.should_be_closed(product, now);
+
+        self.consume_outflow(&jar.product_id, withdrawn_amount, now);
+        self.decrease_product_tvl(&jar.product_id, jar.principal);
+        self.increase_product_tvl(&product_id, principal);
+
+        let lock_id = (withdrawn_amount > 0).then(|| self.increment_and_get_last_lock_id());
+
+        if let Some(lock_id) = lock_id {
+            // Only the withdrawn part is returned to the jar if the transfer fails, the rest is already restaked
+            let jar_before_transfer = Jar {
+                principal: withdrawn_amount,
+                ..withdrawn_jar.clone()
+            };
+            withdrawn_jar.lock(lock_id, LockKind::Withdraw, &jar_before_transfer, withdrawn_amount, now);
+            *self.get_jar_mut_internal(account_id, jar_id) = withdrawn_jar;
+        } else if close_jar {
+            self.delete_jar(account_id, jar_id);
+        } else {
+            *self.get_jar_mut_internal(account_id, jar_id) = withdrawn_jar;
         }
 
-        self.add_new_jar(&account_id, new_jar.clone());
+        if !close_jar || withdrawn_amount > 0 {
+            self.record_activity(jar_id, JarActivityKind::Restake, amount);
+        }
+
+        let new_jar = Jar::create(
+            self.increment_and_get_last_jar_id(),
+            account_id.clone(),
+            product_id,
+            principal,
+            now,
+        );
+
+        self.add_new_jar(account_id, new_jar.clone());
+        self.record_activity(new_jar.id, JarActivityKind::Restake, principal);
 
         emit(EventKind::Restake(RestakeData {
             old_id: jar_id,
             new_id: new_jar.id,
+            old_product_id: jar.product_id.clone(),
+            new_product_id: new_jar.product_id.clone(),
+            principal: U128(principal),
+            rolled_interest: U128(rolled_interest),
+            top_up_amount: U128(top_up),
+            withdrawn_amount: U128(withdrawn_amount),
         }));
 
-        new_jar.into()
+        if let Some(lock_id) = lock_id {
+            // The withdrawal fee is charged only for the withdrawn part of the principal
+            let withdrawn_part = Jar {
+                principal: withdrawn_amount,
+                ..jar
+            };
+
+            return self.transfer_restake_withdraw(
+                account_id,
+                withdrawn_amount,
+                &withdrawn_part,
+                close_jar,
+                lock_id,
+                new_jar.into(),
+            );
+        }
+
+        PromiseOrValue::Value(new_jar.into())
+    }
+
+    /// Restakes the whole principal of a jar, so nothing is withdrawn and no transfer is made.
+    fn restake_whole_principal(
+        &mut self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        product_id: ProductId,
+        top_up: TokenAmount,
+        with_interest: bool,
+    ) -> JarView {
+        let principal = self.get_jar_internal(account_id, jar_id).principal;
+
+        match self.restake_internal(account_id, jar_id, product_id, principal, top_up, with_interest) {
+            PromiseOrValue::Value(new_jar) => new_jar,
+            PromiseOrValue::Promise(_) => unreachable!("Nothing is withdrawn when the whole principal is restaked"),
+        }
     }
 }
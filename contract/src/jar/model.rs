@@ -16,12 +16,17 @@ use near_sdk::{
 };
 
 use crate::{
-    common::{udecimal::UDecimal, Timestamp, MS_IN_YEAR},
-    event::{emit, EventKind, TopUpData},
+    assert::{check_not_frozen, require_ok},
+    common::{mul_div, udecimal::UDecimal, Duration, Timestamp, MS_IN_YEAR},
+    event::{emit, CleanupAccountData, EventKind, TopUpData},
+    history::model::JarActivityKind,
     product::model::{Apy, Product, Terms},
-    Base64VecU8, Contract, JarsStorage, Signature,
+    Base64VecU8, Contract, Signature,
 };
 
+/// The maximum number of penalty intervals stored in a jar.
+pub const MAX_PENALTY_INTERVALS: usize = 10;
+
 /// The `JarTicket` struct represents a request to create a deposit jar for a corresponding product.
 ///
 /// The data from this `JarTicket` is later combined with additional data, including the contract
@@ -43,6 +48,72 @@ pub struct JarTicket {
     pub valid_until: U64,
 }
 
+/// The `JarFilter` struct describes conditions a jar must meet to be included in a paginated listing.
+/// Conditions which aren't set don't filter jars out.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarFilter {
+    /// The products the jar must belong to.
+    pub product_ids: Option<Vec<ProductId>>,
+
+    /// The status the jar must have.
+    pub status: Option<JarStatus>,
+
+    /// The earliest creation date of the jar in milliseconds, inclusive.
+    pub created_from: Option<U64>,
+
+    /// The latest creation date of the jar in milliseconds, exclusive.
+    pub created_to: Option<U64>,
+}
+
+/// The `JarStatus` enum describes the states a jar can be filtered by. The states aren't exclusive,
+/// e.g. a matured jar can be penalized or locked as well.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum JarStatus {
+    /// The jar is Flexible or its lockup term isn't over yet.
+    Active,
+
+    /// The lockup term of the Fixed jar is over.
+    Matured,
+
+    /// Another operation on the jar is in progress.
+    Locked,
+
+    /// The penalty is applied to the jar.
+    Penalized,
+}
+
+impl JarFilter {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.product_ids.is_none() && self.status.is_none() && self.created_from.is_none() && self.created_to.is_none()
+    }
+
+    pub(crate) fn matches(&self, jar: &Jar, product: &Product, now: Timestamp) -> bool {
+        if let Some(product_ids) = &self.product_ids {
+            if !product_ids.contains(&jar.product_id) {
+                return false;
+            }
+        }
+
+        if self.created_from.map_or(false, |from| jar.created_at < from.0)
+            || self.created_to.map_or(false, |to| jar.created_at >= to.0)
+        {
+            return false;
+        }
+
+        let is_matured = !product.is_flexible() && jar.is_liquidable(product, now);
+
+        match self.status {
+            None => true,
+            Some(JarStatus::Active) => !is_matured,
+            Some(JarStatus::Matured) => is_matured,
+            Some(JarStatus::Locked) => jar.is_pending_withdraw,
+            Some(JarStatus::Penalized) => jar.is_penalty_applied,
+        }
+    }
+}
+
 /// The `Jar` struct represents a deposit jar within the smart contract.
 #[derive(
     BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
@@ -77,6 +148,112 @@ pub struct Jar {
 
     /// Indicates whether a penalty has been applied to the jar's owner due to violating product terms.
     pub is_penalty_applied: bool,
+
+    /// Describes the operation which locked the jar, if any. It's used to recover jars stuck in the locked state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<JarLock>,
+
+    /// Describes the freeze of the jar by the manager, if any. Operations on a frozen jar aren't allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freeze: Option<JarFreeze>,
+
+    /// Describes principal requested for withdrawal from a jar of a Flexible product with an unbonding period, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unbonding: Option<JarUnbonding>,
+
+    /// Up to `MAX_PENALTY_INTERVALS` latest periods when a penalty was applied to the jar, from the oldest
+    /// to the newest. The interest is calculated with the fallback APY within these periods.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub penalties: Vec<PenaltyInterval>,
+}
+
+/// The `PenaltyInterval` struct describes a period when a penalty was applied to a jar.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PenaltyInterval {
+    /// The timestamp of when the penalty became effective, measured in milliseconds since Unix epoch.
+    pub started_at: Timestamp,
+
+    /// The timestamp of when the penalty was canceled, measured in milliseconds since Unix epoch.
+    /// It's absent while the penalty is applied.
+    pub ended_at: Option<Timestamp>,
+}
+
+/// The `JarUnbonding` struct describes principal which is moved out of a jar and waits for the end
+/// of the unbonding period to be withdrawn. It doesn't yield interest.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarUnbonding {
+    /// The amount of tokens requested for withdrawal.
+    pub amount: TokenAmount,
+
+    /// The timestamp of when the withdrawal was requested, measured in milliseconds since Unix epoch.
+    pub requested_at: Timestamp,
+
+    /// The timestamp of when the tokens can be withdrawn, measured in milliseconds since Unix epoch.
+    pub available_at: Timestamp,
+}
+
+/// The `JarFreeze` struct describes a freeze of a jar by the manager.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarFreeze {
+    /// The timestamp of when the jar was frozen, measured in milliseconds since Unix epoch.
+    pub frozen_at: Timestamp,
+
+    /// Indicates whether the jar keeps yielding interest while it's frozen.
+    pub accrues_interest: bool,
+}
+
+/// The `JarLock` struct describes an operation involving cross-contract calls which is in progress for a jar.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarLock {
+    /// The unique ID of the lock. Callbacks of the operation are applied only while the jar is held by this lock.
+    pub id: u64,
+
+    /// The kind of the operation.
+    pub kind: LockKind,
+
+    /// The timestamp of when the jar was locked, measured in milliseconds since Unix epoch.
+    pub locked_at: Timestamp,
+
+    /// The state of the jar before the operation.
+    pub snapshot: JarSnapshot,
+
+    /// The principal removed from the product TVL and counted towards its outflow limit by the operation.
+    pub withdrawn_amount: TokenAmount,
+}
+
+/// The `LockKind` enum describes operations that lock a jar until a cross-contract call is completed.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum LockKind {
+    Claim,
+    Withdraw,
+    Exit,
+}
+
+/// The `JarSnapshot` struct stores the part of a jar state which is changed by operations involving
+/// cross-contract calls.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarSnapshot {
+    pub principal: TokenAmount,
+    pub cache: Option<JarCache>,
+    pub claimed_balance: TokenAmount,
 }
 
 /// A cached value that stores calculated interest based on the current state of the jar.
@@ -109,31 +286,176 @@ impl Jar {
             claimed_balance: 0,
             is_pending_withdraw: false,
             is_penalty_applied: false,
+            lock: None,
+            freeze: None,
+            unbonding: None,
+            penalties: vec![],
         }
     }
 
-    pub(crate) fn lock(&mut self) {
+    /// Locks the jar for an operation. The `before` jar is the state of the jar before the operation,
+    /// which can be restored along with `withdrawn_amount` of the product TVL if the operation is never completed.
+    pub(crate) fn lock(
+        &mut self,
+        id: u64,
+        kind: LockKind,
+        before: &Jar,
+        withdrawn_amount: TokenAmount,
+        now: Timestamp,
+    ) {
         self.is_pending_withdraw = true;
+        self.lock = Some(JarLock {
+            id,
+            kind,
+            locked_at: now,
+            snapshot: JarSnapshot {
+                principal: before.principal,
+                cache: before.cache.clone(),
+                claimed_balance: before.claimed_balance,
+            },
+            withdrawn_amount,
+        });
     }
 
     pub(crate) fn unlocked(&self) -> Self {
         Self {
             is_pending_withdraw: false,
+            lock: None,
             ..self.clone()
         }
     }
 
     pub(crate) fn unlock(&mut self) {
         self.is_pending_withdraw = false;
+        self.lock = None;
     }
 
-    pub(crate) fn apply_penalty(&mut self, product: &Product, is_applied: bool, now: Timestamp) {
-        let current_interest = self.get_interest(product, now);
+    /// Restores the state of the jar before the operation that locked it.
+    /// Returns the principal which has to be returned to the product TVL and outflow allowance.
+    pub(crate) fn restore_snapshot(&mut self) -> TokenAmount {
+        let Some(lock) = self.lock.take() else {
+            panic_str(&format!("Jar with id: {} has no snapshot to restore", self.id));
+        };
+
+        self.principal = lock.snapshot.principal;
+        self.cache = lock.snapshot.cache;
+        self.claimed_balance = lock.snapshot.claimed_balance;
+        self.is_pending_withdraw = false;
+
+        lock.withdrawn_amount
+    }
+
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.freeze.is_some()
+    }
+
+    pub(crate) fn freeze(&mut self, accrues_interest: bool, now: Timestamp) {
+        self.freeze = Some(JarFreeze {
+            frozen_at: now,
+            accrues_interest,
+        });
+    }
+
+    /// Unfreezes the jar. If the jar didn't yield interest while it was frozen, the interest accrued before
+    /// the freeze is cached, so the frozen period is skipped.
+    pub(crate) fn unfreeze(&mut self, product: &Product, now: Timestamp) {
+        if self.freeze.as_ref().map_or(false, |freeze| !freeze.accrues_interest) {
+            self.cache = Some(JarCache {
+                updated_at: now,
+                interest: self.get_interest(product, now),
+            });
+        }
+
+        self.freeze = None;
+    }
+
+    pub(crate) fn is_unbonding(&self) -> bool {
+        self.unbonding.is_some()
+    }
+
+    /// Moves `amount` of principal to the unbonding bucket, so it stops yielding interest.
+    pub(crate) fn request_withdraw(&mut self, product: &Product, amount: TokenAmount, now: Timestamp) {
+        let unbonding_period = product.get_unbonding_period().unwrap_or_default();
+
+        *self = self.withdrawn(product, amount, now);
+        self.unbonding = Some(JarUnbonding {
+            amount,
+            requested_at: now,
+            available_at: now + unbonding_period,
+        });
+    }
+
+    /// Returns principal from the unbonding bucket to the jar. Returns the amount of returned principal.
+    pub(crate) fn cancel_withdraw(&mut self, product: &Product, now: Timestamp) -> TokenAmount {
+        let amount = self.unbonding.take().map_or(0, |unbonding| unbonding.amount);
+
+        self.top_up(amount, product, now);
+
+        amount
+    }
+
+    /// Returns the earliest timestamp a penalty can be applied or canceled at, since penalty intervals can't overlap.
+    pub(crate) fn get_penalty_checkpoint(&self) -> Timestamp {
+        let last_penalty_change = self
+            .penalties
+            .last()
+            .map_or(0, |penalty| penalty.ended_at.unwrap_or(penalty.started_at));
+
+        cmp::max(self.created_at, last_penalty_change)
+    }
+
+    /// Applies or cancels a penalty starting from `effective_at`. Does nothing if the penalty status doesn't change.
+    ///
+    /// The interest accrued until `now` is cached, and the interest accrued since `effective_at` is recalculated
+    /// with the new APY based on the current principal. A penalty backdated beyond the last claim takes back
+    /// the cached interest only, already claimed interest isn't taken back.
+    pub(crate) fn apply_penalty(
+        &mut self,
+        product: &Product,
+        is_applied: bool,
+        effective_at: Timestamp,
+        now: Timestamp,
+    ) {
+        if self.is_penalty_applied == is_applied {
+            return;
+        }
+
+        let interest = self.get_interest(product, now);
+
+        let (default_apy, fallback_apy) = match &product.apy {
+            Apy::Constant(apy) => (apy, apy),
+            Apy::Downgradable(apy) => (&apy.default, &apy.fallback),
+        };
+        let recalculated_term = self
+            .get_interest_until_date(product, now)
+            .saturating_sub(cmp::max(effective_at, self.created_at));
+        let difference = self
+            .get_interest_for_term(default_apy, recalculated_term)
+            .saturating_sub(self.get_interest_for_term(fallback_apy, recalculated_term));
 
         self.cache = Some(JarCache {
             updated_at: now,
-            interest: current_interest,
+            interest: if is_applied {
+                interest.saturating_sub(difference)
+            } else {
+                interest + difference
+            },
         });
+
+        if is_applied {
+            self.penalties.push(PenaltyInterval {
+                started_at: effective_at,
+                ended_at: None,
+            });
+        } else if let Some(penalty) = self.penalties.last_mut() {
+            penalty.ended_at = Some(effective_at);
+        }
+
+        // Intervals before the cache update don't affect the interest anymore, so only the latest ones are kept
+        if self.penalties.len() > MAX_PENALTY_INTERVALS {
+            self.penalties.drain(..self.penalties.len() - MAX_PENALTY_INTERVALS);
+        }
+
         self.is_penalty_applied = is_applied;
     }
 
@@ -162,6 +484,17 @@ impl Jar {
         self
     }
 
+    /// Returns a part of claimed interest to the jar, e.g. when the receiver of the claimed tokens refunded them.
+    pub(crate) fn revert_claim(&mut self, amount: TokenAmount) -> &mut Self {
+        self.claimed_balance -= amount;
+
+        if let Some(cache) = self.cache.as_mut() {
+            cache.interest += amount;
+        }
+
+        self
+    }
+
     pub(crate) fn withdrawn(&self, product: &Product, withdrawn_amount: TokenAmount, now: Timestamp) -> Self {
         Self {
             principal: self.principal - withdrawn_amount,
@@ -173,17 +506,93 @@ impl Jar {
         }
     }
 
+    /// Splits the jar into two parts. The new part receives `amount` of principal and a proportional share
+    /// of the accrued interest. Returns the remaining jar and the new jar.
+    pub(crate) fn split(&self, new_id: JarId, amount: TokenAmount, product: &Product, now: Timestamp) -> (Self, Self) {
+        let interest = self.get_interest(product, now);
+        let split_interest = mul_div(interest, amount, self.principal);
+
+        let remaining_jar = Self {
+            principal: self.principal - amount,
+            cache: Some(JarCache {
+                updated_at: now,
+                interest: interest - split_interest,
+            }),
+            ..self.clone()
+        };
+
+        let new_jar = Self {
+            id: new_id,
+            principal: amount,
+            cache: Some(JarCache {
+                updated_at: now,
+                interest: split_interest,
+            }),
+            claimed_balance: 0,
+            unbonding: None,
+            ..self.clone()
+        };
+
+        (remaining_jar, new_jar)
+    }
+
+    /// Creates a jar that combines principal, accrued interest and claimed balance of provided jars.
+    /// For Fixed products the latest creation date is taken, so none of the principal matures earlier than it would
+    /// in the original jars. For Flexible products the earliest creation date is taken.
+    pub(crate) fn merged(id: JarId, jars: &[Jar], product: &Product, now: Timestamp) -> Self {
+        let principal: TokenAmount = jars.iter().map(|jar| jar.principal).sum();
+        let interest: TokenAmount = jars.iter().map(|jar| jar.get_interest(product, now)).sum();
+        let claimed_balance: TokenAmount = jars.iter().map(|jar| jar.claimed_balance).sum();
+
+        // Maturity of a Fixed jar is weighted by principal, so no part of the principal matures earlier
+        let created_at = if product.is_flexible() || principal == 0 {
+            jars.iter().map(|jar| jar.created_at).min().unwrap_or(now)
+        } else {
+            let weighted_sum: u128 = jars.iter().map(|jar| u128::from(jar.created_at) * jar.principal).sum();
+
+            u64::try_from(weighted_sum / principal).unwrap_or(now)
+        };
+
+        let first_jar = &jars[0];
+
+        Self {
+            id,
+            account_id: first_jar.account_id.clone(),
+            product_id: first_jar.product_id.clone(),
+            created_at,
+            principal,
+            cache: Some(JarCache {
+                updated_at: now,
+                interest,
+            }),
+            claimed_balance,
+            is_pending_withdraw: false,
+            is_penalty_applied: first_jar.is_penalty_applied,
+            lock: None,
+            freeze: None,
+            unbonding: None,
+            penalties: if first_jar.is_penalty_applied {
+                vec![PenaltyInterval {
+                    started_at: now,
+                    ended_at: None,
+                }]
+            } else {
+                vec![]
+            },
+        }
+    }
+
     pub(crate) fn should_be_closed(&self, product: &Product, now: Timestamp) -> bool {
         !product.is_flexible() && self.principal == 0 && self.get_interest(product, now) == 0
     }
 
     /// Indicates whether a user can withdraw tokens from the jar at the moment or not.
-    /// For a Flexible product withdrawal is always possible.
+    /// For a Flexible product withdrawal is possible unless it has an unbonding period.
     /// For Fixed product it's defined by the lockup term.
     pub(crate) fn is_liquidable(&self, product: &Product, now: Timestamp) -> bool {
         match product.clone().terms {
             Terms::Fixed(value) => now - self.created_at > value.lockup_term,
-            Terms::Flexible => true,
+            Terms::Flexible(value) => value.unbonding_period.is_none(),
         }
     }
 
@@ -198,22 +607,46 @@ impl Jar {
             (self.created_at, 0)
         };
         let until_date = self.get_interest_until_date(product, now);
-        let effective_term = if until_date > base_date {
-            until_date - base_date
-        } else {
+        if until_date <= base_date {
             return base_interest;
+        }
+
+        let (default_apy, fallback_apy) = match &product.apy {
+            Apy::Constant(apy) => (apy, apy),
+            Apy::Downgradable(apy) => (&apy.default, &apy.fallback),
         };
 
-        let term_in_milliseconds = u128::from(effective_term);
-        let apy = self.get_apy(product);
-        let total_interest = apy * self.principal;
+        let mut interest = 0;
+        let mut cursor = base_date;
+
+        // Penalty intervals are ordered and don't overlap, so the term is split into periods
+        // with the default and the fallback APY.
+        for penalty in &self.penalties {
+            let started_at = penalty.started_at.clamp(cursor, until_date);
+            let ended_at = penalty.ended_at.unwrap_or(until_date).clamp(started_at, until_date);
+
+            interest += self.get_interest_for_term(default_apy, started_at - cursor);
+            interest += self.get_interest_for_term(fallback_apy, ended_at - started_at);
 
-        let interest = (term_in_milliseconds * total_interest) / u128::from(MS_IN_YEAR);
+            cursor = ended_at;
+        }
+
+        interest += self.get_interest_for_term(default_apy, until_date - cursor);
 
         base_interest + interest
     }
 
-    fn get_apy(&self, product: &Product) -> UDecimal {
+    fn get_interest_for_term(&self, apy: &UDecimal, term: Duration) -> TokenAmount {
+        if term == 0 {
+            return 0;
+        }
+
+        let total_interest = apy * self.principal;
+
+        (u128::from(term) * total_interest) / u128::from(MS_IN_YEAR)
+    }
+
+    pub(crate) fn get_apy(&self, product: &Product) -> UDecimal {
         match product.apy.clone() {
             Apy::Constant(apy) => apy,
             Apy::Downgradable(apy) => {
@@ -227,9 +660,14 @@ impl Jar {
     }
 
     fn get_interest_until_date(&self, product: &Product, now: Timestamp) -> Timestamp {
+        let now = match &self.freeze {
+            Some(freeze) if !freeze.accrues_interest => cmp::min(now, freeze.frozen_at),
+            _ => now,
+        };
+
         match product.terms.clone() {
             Terms::Fixed(value) => cmp::min(now, self.created_at + value.lockup_term),
-            Terms::Flexible => now,
+            Terms::Flexible(_) => now,
         }
     }
 }
@@ -255,6 +693,8 @@ impl Contract {
         let jar = Jar::create(id, account_id.clone(), product_id.clone(), amount, now);
 
         self.add_new_jar(&account_id, jar.clone());
+        self.record_activity(id, JarActivityKind::Create, amount);
+        self.increase_product_tvl(product_id, amount);
 
         emit(EventKind::CreateJar(jar.clone()));
 
@@ -262,55 +702,123 @@ impl Contract {
     }
 
     pub(crate) fn top_up(&mut self, account: &AccountId, jar_id: JarId, amount: U128) -> U128 {
-        let jar = self.get_jar_internal(account, jar_id);
-        let product = self.get_product(&jar.product_id).clone();
-
-        require!(product.allows_top_up(), "The product doesn't allow top-ups");
-        product.assert_cap(jar.principal + amount.0);
+        let (_, product) = require_ok(self.check_top_up(account, jar_id, amount.0));
+        let product = product.clone();
 
         let now = env::block_timestamp_ms();
 
+        let initial_storage_usage = self.flush_storage_usage();
         let principal = self
             .get_jar_mut_internal(account, jar_id)
             .top_up(amount.0, &product, now)
             .principal;
+        self.charge_storage_unchecked(account, initial_storage_usage);
+        self.record_activity(jar_id, JarActivityKind::TopUp, amount.0);
+        self.increase_product_tvl(&product.id, amount.0);
 
         emit(EventKind::TopUp(TopUpData { id: jar_id, amount }));
 
         U128(principal)
     }
 
+    /// Checks whether a jar can be topped up with the amount. Returns the jar and its product if it can.
+    pub(crate) fn check_top_up(
+        &self,
+        account: &AccountId,
+        jar_id: JarId,
+        amount: TokenAmount,
+    ) -> Result<(&Jar, &Product), String> {
+        self.check_not_blocked(account)?;
+
+        let jar = self.find_jar(account, jar_id)?;
+        let product = self.find_product(&jar.product_id)?;
+
+        check_not_frozen(jar)?;
+
+        if !product.allows_top_up() {
+            return Err("The product doesn't allow top-ups".to_string());
+        }
+
+        product.check_cap(jar.principal + amount)?;
+
+        Ok((jar, product))
+    }
+
     pub(crate) fn delete_jar(&mut self, account_id: &AccountId, jar_id: JarId) {
-        let jars = self
+        let initial_storage_usage = self.flush_storage_usage();
+
+        let account_jars = self
             .account_jars
             .get_mut(account_id)
             .unwrap_or_else(|| panic_str(&format!("Account '{account_id}' doesn't exist")));
 
         require!(
-            !jars.is_empty(),
+            !account_jars.jar_ids.is_empty(),
             "Trying to delete a jar from account without any jars."
         );
 
-        let jar_position = jars
-            .iter()
-            .position(|j| j.id == jar_id)
-            .unwrap_or_else(|| panic_str(&format!("Jar with id {jar_id} doesn't exist")));
+        require!(
+            account_jars.remove_jar_id(jar_id),
+            format!("Jar with id {jar_id} doesn't exist")
+        );
+
+        self.jars.remove(&jar_id);
+        self.jar_history.remove(&jar_id);
+
+        self.release_storage(account_id, initial_storage_usage);
+    }
+
+    /// Deletes a jar closed by its owner and cleans up the account if it was the last jar of the account.
+    pub(crate) fn close_jar(&mut self, account_id: &AccountId, jar_id: JarId) {
+        self.delete_jar(account_id, jar_id);
+        self.cleanup_account(account_id);
+    }
+
+    /// Removes the index of an account which has no jars, keeping only the last jar ID of the account.
+    /// The account stays registered with the minimum storage balance, which covers the last jar ID,
+    /// and the rest of its storage deposit is refunded.
+    pub(crate) fn cleanup_account(&mut self, account_id: &AccountId) {
+        if !self
+            .account_jars
+            .get(account_id)
+            .map_or(false, |account_jars| account_jars.jar_ids.is_empty())
+        {
+            return;
+        }
+
+        if let Some(account_jars) = self.account_jars.remove(account_id) {
+            self.last_jar_ids.insert(account_id.clone(), account_jars.last_id);
+        }
 
-        jars.swap_remove(jar_position);
+        let storage_refund = self.refund_storage_above_minimum(account_id);
+
+        emit(EventKind::CleanupAccount(CleanupAccountData {
+            account_id: account_id.clone(),
+            storage_refund: U128(storage_refund),
+        }));
     }
 
     pub(crate) fn get_jar_mut_internal(&mut self, account: &AccountId, id: JarId) -> &mut Jar {
-        self.account_jars
-            .get_mut(account)
-            .unwrap_or_else(|| panic_str(&format!("Account '{account}' doesn't exist")))
-            .get_jar_mut(id)
+        require_ok(self.find_jar(account, id));
+
+        self.jars
+            .get_mut(&id)
+            .unwrap_or_else(|| panic_str(&format!("Jar with id: {id} doesn't exist")))
     }
 
     pub(crate) fn get_jar_internal(&self, account: &AccountId, id: JarId) -> &Jar {
-        self.account_jars
-            .get(account)
-            .unwrap_or_else(|| env::panic_str(&format!("Account '{account}' doesn't exist")))
-            .get_jar(id)
+        require_ok(self.find_jar(account, id))
+    }
+
+    pub(crate) fn find_jar(&self, account: &AccountId, id: JarId) -> Result<&Jar, String> {
+        if !self.account_jars.contains_key(account) {
+            return Err(format!("Account '{account}' doesn't exist"));
+        }
+
+        self.jars
+            .get(&id)
+            .filter(|jar| &jar.account_id == account)
+            .ok_or_else(|| format!("Jar with id: {id} doesn't exist"))
     }
 
     pub(crate) fn verify(
@@ -320,7 +828,7 @@ impl Contract {
         ticket: &JarTicket,
         signature: Option<Base64VecU8>,
     ) {
-        let last_jar_id = self.account_jars.get(account_id).map(|jars| jars.last_id);
+        let last_jar_id = self.get_last_jar_id(account_id);
         let product = self.get_product(&ticket.product_id);
 
         if let Some(pk) = &product.public_key {
@@ -376,7 +884,7 @@ impl Contract {
         )
     }
 
-    fn verify_signature(signature: &[u8], product_public_key: &[u8], ticket_hash: &[u8]) -> bool {
+    pub(crate) fn verify_signature(signature: &[u8], product_public_key: &[u8], ticket_hash: &[u8]) -> bool {
         let signature_bytes: &[u8; SIGNATURE_LENGTH] = signature
             .try_into()
             .unwrap_or_else(|_| panic!("Signature must be {SIGNATURE_LENGTH} bytes"));
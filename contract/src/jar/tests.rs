@@ -49,23 +49,28 @@ fn interest_precision() {
 
 #[cfg(test)]
 mod signature_tests {
-    use model::U32;
+    use model::{jar::JarView, U32};
     use near_sdk::{
         json_types::{Base64VecU8, U128, U64},
         test_utils::accounts,
+        PromiseOrValue,
     };
 
     use crate::{
-        common::{tests::Context, udecimal::UDecimal, MS_IN_YEAR},
+        common::{tests::Context, udecimal::UDecimal, MS_IN_MINUTE, MS_IN_YEAR},
+        history::{api::HistoryApi, model::JarActivityKind},
         jar::{
             api::JarApi,
-            model::{Jar, JarTicket},
+            model::{Jar, JarFilter, JarStatus, JarTicket, LockKind},
+            view::{JarsPageView, SkippedJarView},
         },
         product::{
             api::*,
             helpers::MessageSigner,
-            model::{Apy, DowngradableApy, Product},
+            model::{Apy, DowngradableApy, Product, WithdrawalFee},
+            view::TermsView,
         },
+        Contract,
     };
 
     #[test]
@@ -241,7 +246,7 @@ mod signature_tests {
             .with_jars(&[alice_jar.clone()]);
 
         context.switch_account(&admin);
-        context.contract.restake(U32(alice_jar.id));
+        context.contract.restake(U32(alice_jar.id), None);
     }
 
     #[test]
@@ -255,7 +260,7 @@ mod signature_tests {
         let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
 
         context.switch_account(&alice);
-        context.contract.restake(U32(jar.id));
+        context.contract.restake(U32(jar.id), None);
     }
 
     #[test]
@@ -269,7 +274,7 @@ mod signature_tests {
         let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
 
         context.switch_account(&alice);
-        context.contract.restake(U32(jar.id));
+        context.contract.restake(U32(jar.id), None);
     }
 
     #[test]
@@ -290,7 +295,7 @@ mod signature_tests {
         context.set_block_timestamp_in_days(366);
 
         context.switch_account(&alice);
-        context.contract.restake(U32(jar.id));
+        context.contract.restake(U32(jar.id), None);
     }
 
     #[test]
@@ -308,7 +313,7 @@ mod signature_tests {
         context.set_block_timestamp_in_days(366);
 
         context.switch_account(&alice);
-        context.contract.restake(U32(jar.id));
+        context.contract.restake(U32(jar.id), None);
     }
 
     #[test]
@@ -320,12 +325,15 @@ mod signature_tests {
             .with_allows_restaking(true)
             .lockup_term(MS_IN_YEAR);
         let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
-        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
 
         context.set_block_timestamp_in_days(366);
 
         context.switch_account(&alice);
-        context.contract.restake(U32(jar.id));
+        context.contract.restake(U32(jar.id), None);
 
         let alice_jars = context.contract.get_jars_for_account(alice);
 
@@ -352,7 +360,743 @@ mod signature_tests {
         context.set_block_timestamp_in_days(366);
 
         context.switch_account(&alice);
-        context.contract.restake(U32(jar.id));
+        context.contract.restake(U32(jar.id), None);
+    }
+
+    #[test]
+    fn transfer_jar_to_another_account() {
+        let alice = accounts(0);
+        let bob = accounts(1);
+        let admin = accounts(2);
+
+        let product = generate_product("transferable_product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[bob.clone()]);
+
+        context.set_block_timestamp_in_days(100);
+
+        let interest_before = context.contract.get_total_interest(alice.clone()).amount.total;
+
+        context.switch_account(&alice);
+        let transferred =
+            context.with_deposit_yocto(1, |context| context.contract.transfer_jar(U32(jar.id), bob.clone()));
+
+        assert_ne!(jar.id, transferred.id.0);
+        assert_eq!(bob, transferred.account_id);
+        assert_eq!(1_000_000, transferred.principal.0);
+
+        assert!(context.contract.get_jars_for_account(alice).is_empty());
+        assert_eq!(
+            interest_before,
+            context.contract.get_total_interest(bob.clone()).amount.total
+        );
+        assert_eq!(
+            transferred.id.0,
+            context.contract.account_jars.get(&bob).unwrap().last_id
+        );
+    }
+
+    #[test]
+    fn get_jar_by_id_follows_transfer() {
+        let alice = accounts(0);
+        let bob = accounts(1);
+        let admin = accounts(2);
+
+        let product = generate_product("transferable_product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[bob.clone()]);
+
+        assert_eq!(JarView::from(jar.clone()), context.contract.get_jar_by_id(U32(jar.id)));
+        assert_eq!(Some(alice.clone()), context.contract.get_jar_owner(U32(jar.id)));
+
+        context.switch_account(&alice);
+        let transferred =
+            context.with_deposit_yocto(1, |context| context.contract.transfer_jar(U32(jar.id), bob.clone()));
+
+        assert_eq!(None, context.contract.get_jar_owner(U32(jar.id)));
+        assert_eq!(Some(bob), context.contract.get_jar_owner(transferred.id));
+        assert_eq!(transferred, context.contract.get_jar_by_id(transferred.id));
+    }
+
+    #[test]
+    #[should_panic(expected = "Jar with id: 1 doesn't exist")]
+    fn get_not_existing_jar_by_id() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let context = Context::new(admin).with_products(&[product]).with_jars(&[jar]);
+
+        context.contract.get_jar_by_id(U32(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "The product doesn't allow transfers")]
+    fn transfer_jar_of_non_transferable_product() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_premium_product("premium_product", &MessageSigner::new()).transferable(false);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+        context.switch_account(&alice);
+        context.with_deposit_yocto(1, |context| {
+            context.contract.transfer_jar(U32(jar.id), accounts(2));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Another operation on this Jar is in progress")]
+    fn transfer_locked_jar() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("transferable_product");
+        let mut jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        jar.lock(1, LockKind::Claim, &jar.clone(), 0, 0);
+
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+        context.switch_account(&alice);
+        context.with_deposit_yocto(1, |context| {
+            context.contract.transfer_jar(U32(jar.id), accounts(2));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+    fn transfer_jar_without_deposit() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("transferable_product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+        context.switch_account(&alice);
+        context.contract.transfer_jar(U32(jar.id), accounts(2));
+    }
+
+    #[test]
+    fn restake_into_another_product() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let retired_product = generate_product("retired_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(false)
+            .enabled(false);
+        let jar = Jar::generate(0, &alice, &retired_product.id).principal(1_000_000);
+
+        let signer = MessageSigner::new();
+        let new_product = generate_premium_product("premium_product", &signer);
+
+        let mut context = Context::new(admin)
+            .with_products(&[retired_product.clone(), new_product.clone()])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        let ticket = JarTicket {
+            product_id: new_product.id.clone(),
+            valid_until: U64(MS_IN_YEAR * 2),
+        };
+        let signature = signer.sign(&Contract::get_signature_material(
+            &context.owner,
+            &alice,
+            &ticket.product_id,
+            jar.principal,
+            ticket.valid_until.0,
+            Some(0),
+        ));
+
+        context.switch_account(&alice);
+        let new_jar = context
+            .contract
+            .restake_into(U32(jar.id), ticket, Some(Base64VecU8(signature)));
+
+        assert_eq!(new_product.id, new_jar.product_id);
+        assert_eq!(jar.principal, new_jar.principal.0);
+
+        let original_jar = context.contract.get_jar(alice, U32(jar.id));
+        assert_eq!(retired_product.id, original_jar.product_id);
+        assert_eq!(0, original_jar.principal.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not matching signature")]
+    fn restake_into_product_with_invalid_signature() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product").lockup_term(MS_IN_YEAR);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+
+        let signer = MessageSigner::new();
+        let new_product = generate_premium_product("premium_product", &signer);
+
+        let mut context = Context::new(admin)
+            .with_products(&[product, new_product.clone()])
+            .with_jars(&[jar.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        let ticket = JarTicket {
+            product_id: new_product.id,
+            valid_until: U64(MS_IN_YEAR * 2),
+        };
+        let signature = signer.sign(&context.get_signature_material(&alice, &ticket, jar.principal + 1));
+
+        context.switch_account(&alice);
+        context
+            .contract
+            .restake_into(U32(jar.id), ticket, Some(Base64VecU8(signature)));
+    }
+
+    #[test]
+    #[should_panic(expected = "The jar is not mature yet")]
+    fn restake_into_before_maturity() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product").lockup_term(MS_IN_YEAR);
+        let new_product = generate_product("new_product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+
+        let mut context = Context::new(admin)
+            .with_products(&[product, new_product.clone()])
+            .with_jars(&[jar.clone()]);
+
+        let ticket = JarTicket {
+            product_id: new_product.id,
+            valid_until: U64(MS_IN_YEAR),
+        };
+
+        context.switch_account(&alice);
+        context.contract.restake_into(U32(jar.id), ticket, None);
+    }
+
+    #[test]
+    fn restake_part_of_principal_with_interest() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("restakable_product")
+            .apy(Apy::Constant(UDecimal::new(12, 2)))
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        let PromiseOrValue::Value(new_jar) = context.contract.restake_partial(U32(jar.id), U128(600_000), true) else {
+            panic!();
+        };
+
+        assert_eq!(600_000 + 120_000, new_jar.principal.0);
+
+        let jars = context.contract.get_jars_for_account(alice);
+        assert_eq!(1, jars.len());
+        assert_eq!(new_jar.id, jars[0].id);
+    }
+
+    #[test]
+    fn restake_part_of_principal_without_interest() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("restakable_product")
+            .apy(Apy::Constant(UDecimal::new(12, 2)))
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin.clone())
+            .with_products(&[product])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.switch_account(&admin);
+        context.with_deposit_yocto(1, |context| context.contract.set_history_capacity(10));
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        let PromiseOrValue::Value(new_jar) = context.contract.restake_partial(U32(jar.id), U128(600_000), false) else {
+            panic!();
+        };
+
+        assert_eq!(600_000, new_jar.principal.0);
+
+        let activities: Vec<_> = context
+            .contract
+            .get_jar_history(U32(jar.id), None, None)
+            .activities
+            .iter()
+            .map(|activity| (activity.kind, activity.amount.0))
+            .collect();
+        assert_eq!(vec![(JarActivityKind::Restake, 600_000)], activities);
+
+        let original_jar = context.contract.get_jar_internal(&alice, jar.id);
+        assert_eq!(0, original_jar.principal);
+        assert!(!original_jar.is_pending_withdraw);
+        assert_eq!(
+            120_000,
+            context.contract.get_interest(vec![U32(jar.id)], alice).amount.total.0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "To restake more than the jar principal")]
+    fn restake_more_than_principal() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("restakable_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        context.contract.restake_partial(U32(jar.id), U128(1_000_001), false);
+    }
+
+    #[test]
+    fn restake_all_skips_ineligible_jars() {
+        const MS_IN_DAY: u64 = MS_IN_MINUTE * 60 * 24;
+
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("restakable_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true);
+        let not_restakable_product = generate_product("not_restakable_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(false);
+        let jars = [
+            Jar::generate(0, &alice, &product.id).principal(1_000_000),
+            Jar::generate(1, &alice, &product.id)
+                .principal(2_000_000)
+                .created_at(200 * MS_IN_DAY),
+            Jar::generate(2, &alice, &not_restakable_product.id).principal(3_000_000),
+            Jar::generate(3, &alice, &product.id).principal(4_000_000),
+        ];
+        let mut context = Context::new(admin)
+            .with_products(&[product, not_restakable_product])
+            .with_jars(&jars)
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        let result = context.contract.restake_all(None, false);
+
+        let mut restaked: Vec<_> = result.restaked.iter().map(|jar| jar.principal.0).collect();
+        restaked.sort_unstable();
+        assert_eq!(vec![1_000_000, 4_000_000], restaked);
+
+        assert_eq!(2, result.skipped.len());
+        assert!(result
+            .skipped
+            .iter()
+            .any(|skipped| skipped.id.0 == 1 && skipped.reason == "The jar is not mature yet"));
+        assert!(result
+            .skipped
+            .iter()
+            .any(|skipped| skipped.id.0 == 2 && skipped.reason == "The product doesn't support restaking"));
+    }
+
+    #[test]
+    fn restake_jars_with_consolidation() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("restakable_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true);
+        let jars = [
+            Jar::generate(0, &alice, &product.id).principal(1_000_000),
+            Jar::generate(1, &alice, &product.id).principal(2_000_000),
+            Jar::generate(2, &alice, &product.id).principal(3_000_000),
+        ];
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&jars)
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        let result = context.contract.restake_jars(vec![U32(0), U32(1), U32(5)], true);
+
+        assert_eq!(1, result.restaked.len());
+        assert_eq!(3_000_000, result.restaked[0].principal.0);
+        assert_eq!(
+            vec![SkippedJarView {
+                id: U32(5),
+                reason: "Jar with id: 5 doesn't exist".to_string(),
+            }],
+            result.skipped
+        );
+
+        assert_eq!(0, context.contract.get_jar(alice.clone(), U32(0)).principal.0);
+        assert_eq!(0, context.contract.get_jar(alice.clone(), U32(1)).principal.0);
+        assert_eq!(3_000_000, context.contract.get_jar(alice, U32(2)).principal.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Jar IDs must be unique")]
+    fn restake_jars_with_duplicates() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("restakable_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_allows_restaking(true);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        context.contract.restake_jars(vec![U32(0), U32(0)], false);
+    }
+
+    #[test]
+    fn split_jar_shares_principal_and_interest() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product").lockup_term(MS_IN_YEAR);
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&[jar.clone()])
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_days(100);
+
+        let interest_before = context.contract.get_total_interest(alice.clone()).amount.total.0;
+
+        context.switch_account(&alice);
+        let new_jar = context.contract.split_jar(U32(jar.id), U128(250_000));
+
+        assert_eq!(250_000, new_jar.principal.0);
+        assert_eq!(jar.created_at, new_jar.created_at.0);
+        assert_eq!(
+            750_000,
+            context.contract.get_jar(alice.clone(), U32(jar.id)).principal.0
+        );
+
+        let interest = context.contract.get_total_interest(alice.clone()).amount;
+        assert_eq!(interest_before, interest.total.0);
+        assert_eq!(interest_before / 4, interest.detailed[&new_jar.id].0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Split amount must be positive and less than the jar principal")]
+    fn split_jar_with_whole_principal() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar.clone()]);
+
+        context.switch_account(&alice);
+        context.contract.split_jar(U32(jar.id), U128(1_000_000));
+    }
+
+    #[test]
+    fn merge_jars_weights_creation_date_by_principal() {
+        const MS_IN_DAY: u64 = MS_IN_MINUTE * 60 * 24;
+
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product").lockup_term(MS_IN_YEAR);
+        let jars = [
+            Jar::generate(0, &alice, &product.id).principal(1_000_000),
+            Jar::generate(1, &alice, &product.id)
+                .principal(3_000_000)
+                .created_at(100 * MS_IN_DAY),
+        ];
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&jars)
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_days(200);
+
+        let interest_before = context.contract.get_total_interest(alice.clone()).amount.total.0;
+
+        context.switch_account(&alice);
+        let merged_jar = context.contract.merge_jars(vec![U32(0), U32(1)]);
+
+        assert_eq!(4_000_000, merged_jar.principal.0);
+        assert_eq!(75 * MS_IN_DAY, merged_jar.created_at.0);
+
+        let alice_jars = context.contract.get_jars_for_account(alice.clone());
+        assert_eq!(1, alice_jars.len());
+
+        let interest = context.contract.get_total_interest(alice).amount.total.0;
+        assert_eq!(interest_before, interest);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mature jars can't be merged, restake or withdraw them instead")]
+    fn merge_mature_jar_into_fresh_one() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product").lockup_term(MS_IN_YEAR);
+        let jars = [
+            Jar::generate(0, &alice, &product.id).principal(1_000_000),
+            Jar::generate(1, &alice, &product.id)
+                .principal(1_000_000)
+                .created_at(MS_IN_YEAR),
+        ];
+        let mut context = Context::new(admin)
+            .with_products(&[product])
+            .with_jars(&jars)
+            .with_storage_deposit(&[alice.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        context.switch_account(&alice);
+        context.contract.merge_jars(vec![U32(1), U32(0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only jars of the same product can be merged")]
+    fn merge_jars_of_different_products() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product");
+        let another_product = generate_product("another_product");
+        let jars = [
+            Jar::generate(0, &alice, &product.id).principal(1_000_000),
+            Jar::generate(1, &alice, &another_product.id).principal(1_000_000),
+        ];
+        let mut context = Context::new(admin)
+            .with_products(&[product, another_product])
+            .with_jars(&jars);
+
+        context.switch_account(&alice);
+        context.contract.merge_jars(vec![U32(0), U32(1)]);
+    }
+
+    #[test]
+    fn get_jar_details_before_maturity() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("fixed_product")
+            .lockup_term(MS_IN_YEAR)
+            .with_withdrawal_fee(WithdrawalFee::Fix(100));
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[product.clone()])
+            .with_jars(&[jar.clone()]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR / 2);
+
+        let details = context.contract.get_jar_details(alice, U32(0));
+        assert_eq!(details.jar, JarView::from(jar));
+        assert_eq!(Some(U64(MS_IN_YEAR)), details.maturity_date);
+        assert_eq!(0.2, details.apy);
+        assert_eq!(100_000, details.interest.0);
+        assert!(!details.is_pending_withdraw);
+        assert!(!details.is_withdrawable);
+        assert_eq!(100, details.withdrawal_fee.0);
+        assert!(matches!(details.terms, TermsView::Fixed(_)));
+    }
+
+    #[test]
+    fn get_jars_details_for_account_with_penalty() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let signer = MessageSigner::new();
+        let premium_product = generate_premium_product("premium_product", &signer).lockup_term(MS_IN_YEAR);
+        let flexible_product = generate_product("flexible_product").flexible();
+        let mut premium_jar = Jar::generate(0, &alice, &premium_product.id).principal(1_000_000);
+        premium_jar.is_penalty_applied = true;
+        let flexible_jar = Jar::generate(1, &alice, &flexible_product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[premium_product, flexible_product])
+            .with_jars(&[premium_jar, flexible_jar]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        let details = context.contract.get_jars_details_for_account(alice);
+        assert_eq!(2, details.len());
+
+        let premium_details = &details[0];
+        assert_eq!(0.1, premium_details.apy);
+        assert!(premium_details.is_withdrawable);
+        assert_eq!(0, premium_details.withdrawal_fee.0);
+
+        let flexible_details = &details[1];
+        assert_eq!(None, flexible_details.maturity_date);
+        assert!(flexible_details.is_withdrawable);
+        assert!(matches!(flexible_details.terms, TermsView::Flexible));
+    }
+
+    #[test]
+    fn project_interest_at_future_date_and_maturity() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let fixed_product = generate_product("fixed_product").lockup_term(MS_IN_YEAR);
+        let flexible_product = generate_product("flexible_product").flexible();
+        let fixed_jar = Jar::generate(0, &alice, &fixed_product.id).principal(1_000_000);
+        let flexible_jar = Jar::generate(1, &alice, &flexible_product.id).principal(1_000_000);
+        let mut context = Context::new(admin)
+            .with_products(&[fixed_product, flexible_product])
+            .with_jars(&[fixed_jar, flexible_jar]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR / 4);
+
+        let projected = context
+            .contract
+            .get_total_interest_at(alice.clone(), U64(MS_IN_YEAR * 2));
+        assert_eq!(MS_IN_YEAR * 2, projected.timestamp);
+        assert_eq!(200_000, projected.amount.detailed[&U32(0)].0);
+        assert_eq!(400_000, projected.amount.detailed[&U32(1)].0);
+
+        let at_maturity = context.contract.get_total_interest_at_maturity(alice);
+        assert_eq!(1, at_maturity.detailed.len());
+        assert_eq!(200_000, at_maturity.total.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Interest can't be projected to a date in the past")]
+    fn project_interest_at_past_date() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("fixed_product");
+        let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+        let mut context = Context::new(admin).with_products(&[product]).with_jars(&[jar]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR);
+
+        context
+            .contract
+            .get_interest_at(vec![U32(0)], alice, U64(MS_IN_YEAR / 2));
+    }
+
+    #[test]
+    fn get_jars_for_account_paged_in_id_order() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let product = generate_product("product");
+        let jars: Vec<Jar> = [3, 0, 4, 1, 2]
+            .into_iter()
+            .map(|id| Jar::generate(id, &alice, &product.id).principal(1_000_000))
+            .collect();
+        let context = Context::new(admin).with_products(&[product]).with_jars(&jars);
+
+        let page_ids = |page: &JarsPageView| page.jars.iter().map(|jar| jar.id.0).collect::<Vec<_>>();
+
+        let first_page = context
+            .contract
+            .get_jars_for_account_paged(alice.clone(), None, None, Some(2));
+        assert_eq!(vec![0, 1], page_ids(&first_page));
+        assert_eq!(Some(U32(2)), first_page.next_index);
+
+        let second_page =
+            context
+                .contract
+                .get_jars_for_account_paged(alice.clone(), None, first_page.next_index, Some(2));
+        assert_eq!(vec![2, 3], page_ids(&second_page));
+        assert_eq!(Some(U32(4)), second_page.next_index);
+
+        let last_page = context
+            .contract
+            .get_jars_for_account_paged(alice, None, second_page.next_index, Some(2));
+        assert_eq!(vec![4], page_ids(&last_page));
+        assert_eq!(None, last_page.next_index);
+    }
+
+    #[test]
+    fn get_jars_for_account_paged_with_filters() {
+        let alice = accounts(0);
+        let admin = accounts(1);
+
+        let fixed_product = generate_product("fixed_product").lockup_term(MS_IN_YEAR);
+        let flexible_product = generate_product("flexible_product").flexible();
+
+        let matured_jar = Jar::generate(0, &alice, &fixed_product.id).principal(1_000_000);
+        let mut penalized_jar = Jar::generate(1, &alice, &fixed_product.id)
+            .principal(1_000_000)
+            .created_at(MS_IN_YEAR);
+        penalized_jar.is_penalty_applied = true;
+        let mut locked_jar = Jar::generate(2, &alice, &flexible_product.id).principal(1_000_000);
+        locked_jar.lock(1, LockKind::Withdraw, &locked_jar.clone(), 0, 0);
+
+        let mut context = Context::new(admin)
+            .with_products(&[fixed_product.clone(), flexible_product])
+            .with_jars(&[matured_jar, penalized_jar, locked_jar]);
+
+        context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+        let ids = |filter: JarFilter| {
+            context
+                .contract
+                .get_jars_for_account_paged(alice.clone(), Some(filter), None, None)
+                .jars
+                .iter()
+                .map(|jar| jar.id.0)
+                .collect::<Vec<_>>()
+        };
+
+        let by_status = |status: JarStatus| JarFilter {
+            status: Some(status),
+            ..JarFilter::default()
+        };
+
+        assert_eq!(vec![1, 2], ids(by_status(JarStatus::Active)));
+        assert_eq!(vec![0], ids(by_status(JarStatus::Matured)));
+        assert_eq!(vec![2], ids(by_status(JarStatus::Locked)));
+        assert_eq!(vec![1], ids(by_status(JarStatus::Penalized)));
+
+        assert_eq!(
+            vec![0, 1],
+            ids(JarFilter {
+                product_ids: Some(vec![fixed_product.id]),
+                ..JarFilter::default()
+            })
+        );
+
+        assert_eq!(
+            vec![1],
+            ids(JarFilter {
+                created_from: Some(U64(1)),
+                created_to: Some(U64(MS_IN_YEAR + 1)),
+                ..JarFilter::default()
+            })
+        );
     }
 
     #[test]
@@ -394,7 +1138,7 @@ mod helpers {
     use model::TokenAmount;
     use near_sdk::AccountId;
 
-    use crate::jar::model::Jar;
+    use crate::{common::Timestamp, jar::model::Jar};
 
     impl Jar {
         pub(crate) fn generate(id: u32, account_id: &AccountId, product_id: &str) -> Jar {
@@ -408,6 +1152,10 @@ mod helpers {
                 claimed_balance: 0,
                 is_pending_withdraw: false,
                 is_penalty_applied: false,
+                lock: None,
+                freeze: None,
+                unbonding: None,
+                penalties: vec![],
             }
         }
 
@@ -415,5 +1163,10 @@ mod helpers {
             self.principal = principal;
             self
         }
+
+        pub(crate) fn created_at(mut self, created_at: Timestamp) -> Jar {
+            self.created_at = created_at;
+            self
+        }
     }
 }
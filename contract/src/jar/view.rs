@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fmt::Debug};
 
 use model::{
-    jar::{JarIdView, JarView},
+    jar::{JarId, JarIdView, JarView},
     U32,
 };
 use near_sdk::{
@@ -9,7 +9,7 @@ use near_sdk::{
     serde::{Deserialize, Serialize},
 };
 
-use crate::{common::Timestamp, jar::model::Jar};
+use crate::{common::Timestamp, jar::model::Jar, product::view::TermsView};
 
 impl From<Jar> for JarView {
     fn from(value: Jar) -> Self {
@@ -39,6 +39,35 @@ impl From<&Jar> for JarView {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarDetailsView {
+    #[serde(flatten)]
+    pub jar: JarView,
+
+    /// The date when a Fixed jar matures. It's absent for Flexible jars.
+    pub maturity_date: Option<U64>,
+
+    /// The APY currently applied to the jar as a fraction, taking the penalty into account.
+    pub apy: f32,
+
+    /// The interest accrued by the jar and available to claim.
+    pub interest: U128,
+
+    /// Indicates whether another operation on the jar is in progress.
+    pub is_pending_withdraw: bool,
+
+    /// Indicates whether the whole principal of the jar can be withdrawn right now with `withdraw`.
+    /// It takes into account freezes, unbonding periods and outflow limits of the product.
+    pub is_withdrawable: bool,
+
+    /// The fee charged on withdrawal of the whole principal of the jar.
+    pub withdrawal_fee: U128,
+
+    /// The terms of the jar's product.
+    pub terms: TermsView,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
 pub struct AggregatedTokenAmountView {
@@ -52,3 +81,47 @@ pub struct AggregatedInterestView {
     pub amount: AggregatedTokenAmountView,
     pub timestamp: Timestamp,
 }
+
+/// A page of jars ordered by ID. `next_index` is the ID of the first jar of the next page, if there is one.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JarsPageView {
+    pub jars: Vec<JarView>,
+    pub next_index: Option<JarIdView>,
+}
+
+/// Interest claimed from a page of jars. `next_index` is the ID of the first jar of the next page, if there is one.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClaimPageView {
+    pub claimed_amount: U128,
+    pub next_index: Option<JarIdView>,
+}
+
+/// Interest of a page of jars. `next_index` is the ID of the first jar of the next page, if there is one.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InterestPageView {
+    pub interest: AggregatedInterestView,
+    pub next_index: Option<JarIdView>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BatchRestakeView {
+    pub restaked: Vec<JarView>,
+    pub skipped: Vec<SkippedJarView>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SkippedJarView {
+    pub id: JarIdView,
+    pub reason: String,
+}
+
+impl SkippedJarView {
+    pub(crate) fn new(id: JarId, reason: String) -> Self {
+        Self { id: U32(id), reason }
+    }
+}
@@ -1,33 +1,42 @@
-use std::ops::{Deref, DerefMut};
-
 use ed25519_dalek::Signature;
-use model::{jar::JarId, ProductId};
+use model::{jar::JarId, ProductId, TokenAmount};
 use near_sdk::{
     assert_one_yocto,
     borsh::{self, BorshDeserialize, BorshSerialize},
     env,
     json_types::Base64VecU8,
     near_bindgen,
-    store::{LookupMap, UnorderedMap},
-    AccountId, BorshStorageKey, Gas, PanicOnDefault, Promise,
+    store::{LookupMap, LookupSet, UnorderedMap},
+    AccountId, BorshStorageKey, Gas, PanicOnDefault, Promise, StorageUsage,
 };
 use near_self_update::SelfUpdate;
 use product::model::{Apy, Product};
 
-use crate::jar::model::Jar;
+use crate::{
+    common::Duration, history::model::JarHistory, jar::model::Jar, operator::model::Operator,
+    outflow::model::OutflowLimit, storage::model::AccountStorage,
+};
 
 mod assert;
 mod claim;
 mod common;
+mod compliance;
 mod event;
+mod exit;
 mod ft_interface;
 mod ft_receiver;
+mod history;
 mod integration_test;
 mod internal;
 mod jar;
+mod lock;
 mod migration;
+mod operator;
+mod outflow;
 mod penalty;
+mod preview;
 mod product;
+mod storage;
 mod tests;
 mod withdraw;
 
@@ -56,35 +65,101 @@ pub struct Contract {
     /// The last jar ID. Is used as nonce in `get_ticket_hash` method.
     pub last_jar_id: JarId,
 
-    /// A lookup map that associates account IDs with sets of jars owned by each account.
+    /// A lookup map that associates account IDs with IDs of jars owned by each account.
     pub account_jars: LookupMap<AccountId, AccountJars>,
+
+    /// A lookup map that stores every jar under its ID. Jar IDs are unique across all accounts.
+    pub jars: LookupMap<JarId, Jar>,
+
+    /// A lookup map that associates account IDs with operators approved to manage their jars.
+    pub operators: LookupMap<AccountId, Vec<Operator>>,
+
+    /// A lookup map that associates account IDs with NEAR tokens covering storage used by their jars.
+    pub storage_accounts: LookupMap<AccountId, AccountStorage>,
+
+    /// The number of bytes used by a registration of an account with the longest possible ID.
+    pub account_storage_usage: StorageUsage,
+
+    /// A lookup map that keeps last jar IDs of accounts cleaned up after all of their jars were closed.
+    /// The last jar ID is used as a nonce for signed tickets, so it must outlive the account's jars.
+    pub last_jar_ids: LookupMap<AccountId, JarId>,
+
+    /// The number of the latest activities stored for each jar. Zero means that the history is disabled.
+    pub history_capacity: u32,
+
+    /// A lookup map that stores the latest activities of jars under their IDs.
+    pub jar_history: LookupMap<JarId, JarHistory>,
+
+    /// A set of accounts which aren't allowed to operate their jars.
+    pub blocked_accounts: LookupSet<AccountId>,
+
+    /// Total principal of jars per product, including principal waiting for the end of an unbonding period.
+    /// It's tracked for jars created or migrated since the field was introduced.
+    pub product_tvls: LookupMap<ProductId, TokenAmount>,
+
+    /// Limits on principal withdrawn from products within a time window.
+    pub outflow_limits: LookupMap<ProductId, OutflowLimit>,
+
+    /// The maximum duration in milliseconds a penalty can be backdated by. Zero means that penalties
+    /// are effective only since the moment they're set.
+    pub max_penalty_lookback: Duration,
+
+    /// Public keys verifying penalty statements of products. A product without a penalty key uses its public key.
+    pub penalty_keys: LookupMap<ProductId, Vec<u8>>,
+
+    /// The nonce of the last penalty statement applied to each account within each product.
+    pub penalty_nonces: LookupMap<(ProductId, AccountId), u64>,
+
+    /// The ID of the last jar lock. Callbacks of cross-contract calls apply only to jars which are still held
+    /// by the lock of their operation.
+    pub last_lock_id: u64,
 }
 
+/// The `AccountJars` struct is an index of jars owned by an account. The jars themselves are stored
+/// separately, so an operation on a single jar doesn't load the rest of them.
 #[derive(Default, BorshDeserialize, BorshSerialize)]
 pub struct AccountJars {
     /// The last jar ID. Is used as nonce in `get_ticket_hash` method.
     pub last_id: JarId,
-    pub jars: Vec<Jar>,
-}
 
-impl Deref for AccountJars {
-    type Target = Vec<Jar>;
+    /// IDs of the jars sorted in ascending order, so pages of jars can be found with a binary search.
+    pub jar_ids: Vec<JarId>,
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.jars
+impl AccountJars {
+    pub(crate) fn insert_jar_id(&mut self, jar_id: JarId) {
+        if let Err(position) = self.jar_ids.binary_search(&jar_id) {
+            self.jar_ids.insert(position, jar_id);
+        }
     }
-}
 
-impl DerefMut for AccountJars {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.jars
+    pub(crate) fn remove_jar_id(&mut self, jar_id: JarId) -> bool {
+        match self.jar_ids.binary_search(&jar_id) {
+            Ok(position) => {
+                self.jar_ids.remove(position);
+                true
+            }
+            Err(_) => false,
+        }
     }
 }
 
 #[derive(BorshStorageKey, BorshSerialize)]
 pub(crate) enum StorageKey {
     Products,
+    /// Jars of accounts which aren't migrated to per-jar storage yet, see `migrate_accounts`.
+    AccountJarsLegacy,
+    Operators,
+    Jars,
     AccountJars,
+    StorageAccounts,
+    LastJarIds,
+    JarHistory,
+    BlockedAccounts,
+    ProductTvls,
+    OutflowLimits,
+    PenaltyKeys,
+    PenaltyNonces,
 }
 
 #[near_bindgen]
@@ -93,32 +168,30 @@ impl Contract {
     #[private]
     #[must_use]
     pub fn init(token_account_id: AccountId, fee_account_id: AccountId, manager: AccountId) -> Self {
-        Self {
+        let mut contract = Self {
             token_account_id,
             fee_account_id,
             manager,
             products: UnorderedMap::new(StorageKey::Products),
             account_jars: LookupMap::new(StorageKey::AccountJars),
+            jars: LookupMap::new(StorageKey::Jars),
+            operators: LookupMap::new(StorageKey::Operators),
             last_jar_id: 0,
-        }
-    }
-}
-
-pub(crate) trait JarsStorage {
-    fn get_jar(&self, id: JarId) -> &Jar;
-    fn get_jar_mut(&mut self, id: JarId) -> &mut Jar;
-}
-
-impl JarsStorage for Vec<Jar> {
-    fn get_jar(&self, id: JarId) -> &Jar {
-        self.iter()
-            .find(|jar| jar.id == id)
-            .unwrap_or_else(|| env::panic_str(&format!("Jar with id: {id} doesn't exist")))
-    }
-
-    fn get_jar_mut(&mut self, id: JarId) -> &mut Jar {
-        self.iter_mut()
-            .find(|jar| jar.id == id)
-            .unwrap_or_else(|| env::panic_str(&format!("Jar with id: {id} doesn't exist")))
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
+            account_storage_usage: 0,
+            last_jar_ids: LookupMap::new(StorageKey::LastJarIds),
+            history_capacity: 0,
+            jar_history: LookupMap::new(StorageKey::JarHistory),
+            blocked_accounts: LookupSet::new(StorageKey::BlockedAccounts),
+            product_tvls: LookupMap::new(StorageKey::ProductTvls),
+            outflow_limits: LookupMap::new(StorageKey::OutflowLimits),
+            max_penalty_lookback: 0,
+            penalty_keys: LookupMap::new(StorageKey::PenaltyKeys),
+            penalty_nonces: LookupMap::new(StorageKey::PenaltyNonces),
+            last_lock_id: 0,
+        };
+        contract.measure_account_storage_usage();
+
+        contract
     }
 }
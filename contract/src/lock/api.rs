@@ -0,0 +1,131 @@
+use model::jar::JarIdView;
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::{
+    common::{Duration, MS_IN_MINUTE},
+    event::{emit, EventKind, UnlockJarsData},
+    lock::view::LockedJarView,
+    Contract, ContractExt,
+};
+
+/// The period after which the owner of a jar can unlock it on their own.
+const SELF_UNLOCK_TIMEOUT: Duration = 7 * 24 * 60 * MS_IN_MINUTE;
+
+/// The `LockApi` trait defines methods for recovering jars stuck in the locked state, which may happen
+/// if a callback of a cross-contract call fails, for example, because of insufficient gas.
+pub trait LockApi {
+    /// Unlocks jars of the specified account. This method can only be called by the manager.
+    ///
+    /// Before unlocking, the manager must check whether the token transfer of the operation that locked
+    /// the jars has been completed. If it has failed, the state of the jars before the operation should be restored.
+    /// Restored principal is returned to the product TVL and the outflow allowance.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account of user which owns the jars.
+    /// * `jar_ids` - The IDs of the locked jars.
+    /// * `restore` - Whether the state of the jars before the operation that locked them is restored.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the function is called by an account other than the manager.
+    /// - If any of the jars isn't locked.
+    /// - If `restore` is set and any of the jars has no stored state to restore.
+    fn force_unlock(&mut self, account_id: AccountId, jar_ids: Vec<JarIdView>, restore: bool);
+
+    /// Unlocks jars of the calling account which have been locked for longer than 7 days.
+    /// The jars keep their current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_ids` - The IDs of the locked jars.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if any of the jars isn't locked or has been locked for less than 7 days.
+    fn unlock_jars(&mut self, jar_ids: Vec<JarIdView>);
+
+    /// Retrieves jars of the specified account which have been locked for longer than the specified period.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jars owner.
+    /// * `older_than_minutes` - The minimal period in minutes for which the jars have been locked.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<LockedJarView>` describing the locked jars.
+    fn get_locked_jars(&self, account_id: AccountId, older_than_minutes: u64) -> Vec<LockedJarView>;
+}
+
+#[near_bindgen]
+impl LockApi for Contract {
+    fn force_unlock(&mut self, account_id: AccountId, jar_ids: Vec<JarIdView>, restore: bool) {
+        self.assert_manager();
+
+        for jar_id in &jar_ids {
+            let jar = self.get_jar_mut_internal(&account_id, jar_id.0);
+
+            require!(jar.is_pending_withdraw, format!("Jar with id: {} isn't locked", jar.id));
+
+            if !restore {
+                jar.unlock();
+                continue;
+            }
+
+            let restored_principal = jar.restore_snapshot();
+            let product_id = jar.product_id.clone();
+
+            self.release_outflow(&product_id, restored_principal);
+            self.increase_product_tvl(&product_id, restored_principal);
+        }
+
+        emit(EventKind::UnlockJars(UnlockJarsData {
+            account_id,
+            ids: jar_ids.into_iter().map(|id| id.0).collect(),
+            is_restored: restore,
+        }));
+    }
+
+    fn unlock_jars(&mut self, jar_ids: Vec<JarIdView>) {
+        let account_id = env::predecessor_account_id();
+        let now = env::block_timestamp_ms();
+
+        for jar_id in &jar_ids {
+            let jar = self.get_jar_mut_internal(&account_id, jar_id.0);
+
+            require!(jar.is_pending_withdraw, format!("Jar with id: {} isn't locked", jar.id));
+            require!(
+                jar.lock
+                    .as_ref()
+                    .map_or(false, |lock| now - lock.locked_at >= SELF_UNLOCK_TIMEOUT),
+                format!("Jar with id: {} can't be unlocked yet", jar.id)
+            );
+
+            jar.unlock();
+        }
+
+        emit(EventKind::UnlockJars(UnlockJarsData {
+            account_id,
+            ids: jar_ids.into_iter().map(|id| id.0).collect(),
+            is_restored: false,
+        }));
+    }
+
+    fn get_locked_jars(&self, account_id: AccountId, older_than_minutes: u64) -> Vec<LockedJarView> {
+        let now = env::block_timestamp_ms();
+        let min_lock_duration = older_than_minutes * MS_IN_MINUTE;
+
+        self.account_jars(&account_id)
+            .into_iter()
+            .filter(|jar| jar.is_pending_withdraw)
+            .filter(|jar| {
+                jar.lock
+                    .as_ref()
+                    .map_or(true, |lock| now - lock.locked_at >= min_lock_duration)
+            })
+            .map(Into::into)
+            .collect()
+    }
+}
@@ -0,0 +1,146 @@
+#![cfg(test)]
+
+use model::U32;
+use near_sdk::{test_utils::accounts, AccountId};
+
+use crate::{
+    common::tests::Context,
+    jar::model::{Jar, JarUnbonding, LockKind},
+    lock::api::LockApi,
+    product::model::Product,
+};
+
+fn prepare_stuck_withdrawal() -> (AccountId, AccountId, Context) {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("product").enabled(true);
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+
+    let mut locked_jar = jar.clone();
+    locked_jar.principal = 0;
+    locked_jar.lock(1, LockKind::Withdraw, &jar, 1_000_000, 0);
+
+    let context = Context::new(admin.clone())
+        .with_products(&[product])
+        .with_jars(&[locked_jar]);
+
+    (alice, admin, context)
+}
+
+#[test]
+fn force_unlock_with_restore() {
+    let (alice, admin, mut context) = prepare_stuck_withdrawal();
+
+    context.switch_account(&admin);
+    context.contract.force_unlock(alice.clone(), vec![U32(0)], true);
+
+    let jar = context.contract.get_jar_internal(&alice, 0);
+    assert_eq!(1_000_000, jar.principal);
+    assert!(!jar.is_pending_withdraw);
+    assert_eq!(None, jar.lock);
+    assert_eq!(1_000_000, context.contract.get_product_tvl(&jar.product_id));
+}
+
+#[test]
+fn force_unlock_with_restore_after_finalize_withdraw() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("product").enabled(true).flexible();
+    let mut jar = Jar::generate(0, &alice, &product.id).principal(500_000);
+    jar.unbonding = Some(JarUnbonding {
+        amount: 500_000,
+        requested_at: 0,
+        available_at: 0,
+    });
+
+    let mut locked_jar = jar.clone();
+    locked_jar.lock(1, LockKind::Withdraw, &jar, 500_000, 0);
+
+    let mut context = Context::new(admin.clone())
+        .with_products(&[product.clone()])
+        .with_jars(&[locked_jar]);
+
+    context.switch_account(&admin);
+    context.contract.force_unlock(alice.clone(), vec![U32(0)], true);
+
+    let jar = context.contract.get_jar_internal(&alice, 0);
+    assert_eq!(500_000, jar.principal);
+    assert!(jar.unbonding.is_some());
+    assert_eq!(500_000, context.contract.get_product_tvl(&product.id));
+}
+
+#[test]
+fn failed_withdraw_callback_after_force_unlock() {
+    let (alice, admin, mut context) = prepare_stuck_withdrawal();
+
+    context.switch_account(&admin);
+    context.contract.force_unlock(alice.clone(), vec![U32(0)], true);
+
+    let withdrawn = context
+        .contract
+        .after_withdraw_internal(alice.clone(), 0, 1, false, 1_000_000, None, false, 0);
+    assert_eq!(0, withdrawn.withdrawn_amount.0);
+
+    let jar = context.contract.get_jar_internal(&alice, 0);
+    assert_eq!(1_000_000, jar.principal);
+    assert_eq!(1_000_000, context.contract.get_product_tvl(&jar.product_id));
+}
+
+#[test]
+fn force_unlock_without_restore() {
+    let (alice, admin, mut context) = prepare_stuck_withdrawal();
+
+    context.switch_account(&admin);
+    context.contract.force_unlock(alice.clone(), vec![U32(0)], false);
+
+    let jar = context.contract.get_jar_internal(&alice, 0);
+    assert_eq!(0, jar.principal);
+    assert!(!jar.is_pending_withdraw);
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn force_unlock_by_not_manager() {
+    let (alice, _, mut context) = prepare_stuck_withdrawal();
+
+    context.switch_account(&alice);
+    context.contract.force_unlock(alice.clone(), vec![U32(0)], true);
+}
+
+#[test]
+#[should_panic(expected = "Jar with id: 0 can't be unlocked yet")]
+fn self_unlock_before_timeout() {
+    let (alice, _, mut context) = prepare_stuck_withdrawal();
+
+    context.set_block_timestamp_in_days(6);
+    context.switch_account(&alice);
+    context.contract.unlock_jars(vec![U32(0)]);
+}
+
+#[test]
+fn self_unlock_after_timeout() {
+    let (alice, _, mut context) = prepare_stuck_withdrawal();
+
+    context.set_block_timestamp_in_days(7);
+    context.switch_account(&alice);
+    context.contract.unlock_jars(vec![U32(0)]);
+
+    let jar = context.contract.get_jar_internal(&alice, 0);
+    assert_eq!(0, jar.principal);
+    assert!(!jar.is_pending_withdraw);
+}
+
+#[test]
+fn get_locked_jars_older_than() {
+    let (alice, _, mut context) = prepare_stuck_withdrawal();
+
+    context.set_block_timestamp_in_minutes(30);
+
+    assert!(context.contract.get_locked_jars(alice.clone(), 60).is_empty());
+
+    let locked_jars = context.contract.get_locked_jars(alice, 30);
+    assert_eq!(1, locked_jars.len());
+    assert_eq!(Some(LockKind::Withdraw), locked_jars[0].kind);
+}
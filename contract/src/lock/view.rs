@@ -0,0 +1,25 @@
+use model::{jar::JarIdView, U32};
+use near_sdk::{
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+};
+
+use crate::jar::model::{Jar, LockKind};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockedJarView {
+    pub id: JarIdView,
+    pub kind: Option<LockKind>,
+    pub locked_at: Option<U64>,
+}
+
+impl From<&Jar> for LockedJarView {
+    fn from(value: &Jar) -> Self {
+        Self {
+            id: U32(value.id),
+            kind: value.lock.as_ref().map(|lock| lock.kind),
+            locked_at: value.lock.as_ref().map(|lock| U64(lock.locked_at)),
+        }
+    }
+}
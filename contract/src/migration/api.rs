@@ -1,13 +1,125 @@
-use model::TokenAmount;
-use near_sdk::{json_types::U128, require};
+use std::cmp;
+
+use model::{ProductId, TokenAmount};
+use near_sdk::{
+    env,
+    json_types::U128,
+    near_bindgen, require,
+    store::{LookupMap, LookupSet, UnorderedMap},
+    AccountId,
+};
 
 use crate::{
     event::{emit, EventKind, MigrationEventItem},
     jar::model::Jar,
-    migration::model::CeFiJar,
-    Contract,
+    migration::model::{AccountJarsV1, CeFiJar, ContractV1, ProductV1},
+    Contract, ContractExt, StorageKey,
 };
 
+#[near_bindgen]
+impl Contract {
+    /// Migrates the contract state from the layout where all jars of an account were stored in a single vector.
+    ///
+    /// Products are migrated right away. Jars are moved to per-jar storage with `migrate_accounts`,
+    /// since accounts can't be enumerated on chain. Jars of an account aren't available until
+    /// the account is migrated, so `migrate_accounts` must be called for all accounts right after
+    /// this method.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the contract state doesn't match the previous layout.
+    #[init(ignore_state)]
+    #[private]
+    #[must_use]
+    pub fn migrate_state() -> Self {
+        let mut old_state: ContractV1 =
+            env::state_read().unwrap_or_else(|| env::panic_str("Failed to read the contract state"));
+
+        let old_products: Vec<(ProductId, ProductV1)> = old_state.products.drain().collect();
+        old_state.products.flush();
+
+        let mut products = UnorderedMap::new(StorageKey::Products);
+        for (product_id, product) in old_products {
+            products.insert(product_id, product.into());
+        }
+
+        let mut contract = Self {
+            token_account_id: old_state.token_account_id,
+            fee_account_id: old_state.fee_account_id,
+            manager: old_state.manager,
+            products,
+            last_jar_id: old_state.last_jar_id,
+            account_jars: LookupMap::new(StorageKey::AccountJars),
+            jars: LookupMap::new(StorageKey::Jars),
+            operators: LookupMap::new(StorageKey::Operators),
+            storage_accounts: LookupMap::new(StorageKey::StorageAccounts),
+            account_storage_usage: 0,
+            last_jar_ids: LookupMap::new(StorageKey::LastJarIds),
+            history_capacity: 0,
+            jar_history: LookupMap::new(StorageKey::JarHistory),
+            blocked_accounts: LookupSet::new(StorageKey::BlockedAccounts),
+            product_tvls: LookupMap::new(StorageKey::ProductTvls),
+            outflow_limits: LookupMap::new(StorageKey::OutflowLimits),
+            max_penalty_lookback: 0,
+            penalty_keys: LookupMap::new(StorageKey::PenaltyKeys),
+            penalty_nonces: LookupMap::new(StorageKey::PenaltyNonces),
+            last_lock_id: 0,
+        };
+        contract.measure_account_storage_usage();
+
+        contract
+    }
+
+    /// Moves jars of the provided accounts from the previous storage layout to per-jar storage.
+    /// Accounts which are already migrated or have never had jars are skipped, so the method can be
+    /// safely called several times with overlapping lists. Migrated accounts are registered for storage
+    /// and their jars are covered with a sponsored allowance. Accounts with locked jars can't be migrated
+    /// until the operations on these jars are completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_ids` - A `Vec<AccountId>` of accounts to migrate.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager or if any of the accounts
+    /// has a locked jar.
+    pub fn migrate_accounts(&mut self, account_ids: Vec<AccountId>) {
+        self.assert_manager();
+
+        let mut old_account_jars: LookupMap<AccountId, AccountJarsV1> = LookupMap::new(StorageKey::AccountJarsLegacy);
+
+        for account_id in account_ids {
+            let Some(old_jars) = old_account_jars.remove(&account_id) else {
+                continue;
+            };
+
+            // There is no state to restore a locked jar from, so its operation has to complete first
+            if let Some(jar) = old_jars.jars.iter().find(|jar| jar.is_pending_withdraw) {
+                env::panic_str(&format!(
+                    "Jar with id: {} is locked, migrate the account after its operation is completed",
+                    jar.id
+                ));
+            }
+
+            let initial_storage_usage = self.flush_storage_usage();
+
+            let account_jars = self.account_jars_entry(&account_id);
+            account_jars.last_id = cmp::max(account_jars.last_id, old_jars.last_id);
+            for jar in &old_jars.jars {
+                account_jars.insert_jar_id(jar.id);
+            }
+
+            for jar in old_jars.jars {
+                self.increase_product_tvl(&jar.product_id, jar.principal);
+                self.jars.insert(jar.id, jar.into());
+            }
+
+            self.sponsor_migrated_storage(&account_id, initial_storage_usage);
+        }
+    }
+}
+
 impl Contract {
     /// Migrates `CeFi Jars` to create `DeFi Jars`.
     ///
@@ -35,6 +147,9 @@ impl Contract {
     ///
     /// 3. Panics in case of unauthorized access by non-admin users.
     ///
+    /// 4. If an owner of a migrated jar isn't registered for storage or its storage balance, including
+    ///    a sponsored allowance, doesn't cover the jar.
+    ///
     /// # Authorization
     ///
     /// This method can only be called by the Contract Admin. Unauthorized access will result in a panic.
@@ -51,8 +166,6 @@ impl Contract {
 
             let id = self.increment_and_get_last_jar_id();
 
-            let account_jars = self.account_jars.entry(ce_fi_jar.account_id.clone()).or_default();
-
             let jar = Jar {
                 id,
                 account_id: ce_fi_jar.account_id,
@@ -63,6 +176,10 @@ impl Contract {
                 claimed_balance: 0,
                 is_pending_withdraw: false,
                 is_penalty_applied: false,
+                lock: None,
+                freeze: None,
+                unbonding: None,
+                penalties: vec![],
             };
 
             total_amount += jar.principal;
@@ -73,7 +190,15 @@ impl Contract {
                 account_id: jar.account_id.clone(),
             });
 
-            account_jars.push(jar);
+            let account_id = jar.account_id.clone();
+            let initial_storage_usage = self.flush_storage_usage();
+
+            // Migrated jars don't update the last jar ID of an account, since it's used as a nonce for tickets
+            self.account_jars_entry(&account_id).insert_jar_id(jar.id);
+            self.increase_product_tvl(&jar.product_id, jar.principal);
+            self.jars.insert(jar.id, jar);
+
+            self.charge_storage(&account_id, initial_storage_usage);
         }
 
         require!(
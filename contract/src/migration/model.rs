@@ -1,11 +1,18 @@
-use model::ProductId;
+use model::{jar::JarId, ProductId, TokenAmount};
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     json_types::{U128, U64},
     serde::{Deserialize, Serialize},
+    store::{LookupMap, UnorderedMap},
     AccountId,
 };
 
+use crate::{
+    common::Timestamp,
+    jar::model::{Jar, JarCache, PenaltyInterval},
+    product::model::{Apy, Cap, FixedProductTerms, FlexibleProductTerms, Product, Terms, WithdrawalFee},
+};
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct CeFiJar {
@@ -15,3 +22,108 @@ pub struct CeFiJar {
     pub principal: U128,
     pub created_at: U64,
 }
+
+/// The layout of the contract state before jars were moved to per-jar storage.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(crate) struct ContractV1 {
+    pub token_account_id: AccountId,
+    pub fee_account_id: AccountId,
+    pub manager: AccountId,
+    pub products: UnorderedMap<ProductId, ProductV1>,
+    pub last_jar_id: JarId,
+    pub account_jars: LookupMap<AccountId, AccountJarsV1>,
+}
+
+/// The layout of a product before jar transfers were introduced.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(crate) struct ProductV1 {
+    pub id: ProductId,
+    pub apy: Apy,
+    pub cap: Cap,
+    pub terms: TermsV1,
+    pub withdrawal_fee: Option<WithdrawalFee>,
+    pub public_key: Option<Vec<u8>>,
+    pub is_enabled: bool,
+}
+
+impl From<ProductV1> for Product {
+    fn from(value: ProductV1) -> Self {
+        Self {
+            id: value.id,
+            apy: value.apy,
+            cap: value.cap,
+            terms: value.terms.into(),
+            withdrawal_fee: value.withdrawal_fee,
+            public_key: value.public_key,
+            is_enabled: value.is_enabled,
+            is_transferable: true,
+        }
+    }
+}
+
+/// The layout of product terms before Flexible products got an unbonding period.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(crate) enum TermsV1 {
+    Fixed(FixedProductTerms),
+    Flexible,
+}
+
+impl From<TermsV1> for Terms {
+    fn from(value: TermsV1) -> Self {
+        match value {
+            TermsV1::Fixed(terms) => Terms::Fixed(terms),
+            TermsV1::Flexible => Terms::Flexible(FlexibleProductTerms { unbonding_period: None }),
+        }
+    }
+}
+
+/// The layout of account jars when all jars of an account were stored in a single vector.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(crate) struct AccountJarsV1 {
+    pub last_id: JarId,
+    pub jars: Vec<JarV1>,
+}
+
+/// The layout of a jar before locks were introduced.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub(crate) struct JarV1 {
+    pub id: JarId,
+    pub account_id: AccountId,
+    pub product_id: ProductId,
+    pub created_at: Timestamp,
+    pub principal: TokenAmount,
+    pub cache: Option<JarCache>,
+    pub claimed_balance: TokenAmount,
+    pub is_pending_withdraw: bool,
+    pub is_penalty_applied: bool,
+}
+
+impl From<JarV1> for Jar {
+    fn from(value: JarV1) -> Self {
+        // Interest accrued before the last penalty change is cached, so the penalty is effective since then
+        let penalties = if value.is_penalty_applied {
+            vec![PenaltyInterval {
+                started_at: value.cache.as_ref().map_or(value.created_at, |cache| cache.updated_at),
+                ended_at: None,
+            }]
+        } else {
+            vec![]
+        };
+
+        Self {
+            id: value.id,
+            account_id: value.account_id,
+            product_id: value.product_id,
+            created_at: value.created_at,
+            principal: value.principal,
+            cache: value.cache,
+            claimed_balance: value.claimed_balance,
+            is_pending_withdraw: value.is_pending_withdraw,
+            is_penalty_applied: value.is_penalty_applied,
+            lock: None,
+            freeze: None,
+            unbonding: None,
+            penalties,
+        }
+    }
+}
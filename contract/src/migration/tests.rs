@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use model::U32;
+use near_contract_standards::storage_management::StorageManagement;
+use near_sdk::{
+    env,
+    json_types::U128,
+    store::{LookupMap, UnorderedMap},
+    test_utils::accounts,
+};
+
+use crate::{
+    common::tests::Context,
+    jar::{api::JarApi, model::Jar},
+    migration::model::{AccountJarsV1, ContractV1, JarV1, ProductV1, TermsV1},
+    product::model::{Product, Terms, WithdrawalFee},
+    Contract, StorageKey,
+};
+
+fn write_state_v1(product: &Product, jars: Vec<Jar>, last_id: u32) {
+    let admin = accounts(1);
+
+    let mut products = UnorderedMap::new(StorageKey::Products);
+    products.insert(
+        product.id.clone(),
+        ProductV1 {
+            id: product.id.clone(),
+            apy: product.apy.clone(),
+            cap: product.cap.clone(),
+            terms: match &product.terms {
+                Terms::Fixed(terms) => TermsV1::Fixed(terms.clone()),
+                Terms::Flexible(_) => TermsV1::Flexible,
+            },
+            withdrawal_fee: product.withdrawal_fee.clone(),
+            public_key: product.public_key.clone(),
+            is_enabled: product.is_enabled,
+        },
+    );
+
+    let mut account_jars = LookupMap::new(StorageKey::AccountJarsLegacy);
+    account_jars.insert(
+        jars[0].account_id.clone(),
+        AccountJarsV1 {
+            last_id,
+            jars: jars
+                .into_iter()
+                .map(|jar| JarV1 {
+                    id: jar.id,
+                    account_id: jar.account_id,
+                    product_id: jar.product_id,
+                    created_at: jar.created_at,
+                    principal: jar.principal,
+                    cache: jar.cache,
+                    claimed_balance: jar.claimed_balance,
+                    is_pending_withdraw: jar.is_pending_withdraw,
+                    is_penalty_applied: jar.is_penalty_applied,
+                })
+                .collect(),
+        },
+    );
+
+    let state = ContractV1 {
+        token_account_id: accounts(2),
+        fee_account_id: accounts(3),
+        manager: admin,
+        products,
+        last_jar_id: last_id,
+        account_jars,
+    };
+
+    env::state_write(&state);
+}
+
+#[test]
+fn migrate_state_and_accounts() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("product").enabled(true).transferable(false);
+    let jars = vec![
+        Jar::generate(1, &alice, &product.id).principal(1_000_000),
+        Jar::generate(2, &alice, &product.id).principal(2_000_000),
+    ];
+
+    let mut context = Context::new(admin.clone());
+    write_state_v1(&product, jars.clone(), 2);
+
+    context.contract = Contract::migrate_state();
+
+    let migrated_product = context.contract.get_product(&product.id);
+    assert!(migrated_product.is_transferable);
+    assert_eq!(admin, context.contract.manager);
+    assert_eq!(2, context.contract.last_jar_id);
+    assert!(context.contract.get_jars_for_account(alice.clone()).is_empty());
+
+    context.switch_account(&admin);
+    context.contract.migrate_accounts(vec![alice.clone(), alice.clone()]);
+
+    let migrated_jars = context.contract.get_jars_for_account(alice.clone());
+    assert_eq!(2, migrated_jars.len());
+    assert_eq!(2_000_000, context.contract.get_jar(alice.clone(), U32(2)).principal.0);
+    assert_eq!(Some(alice.clone()), context.contract.get_jar_owner(U32(1)));
+    assert_eq!(2, context.contract.account_jars.get(&alice).unwrap().last_id);
+}
+
+#[test]
+fn migrate_flexible_product() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("flexible_product")
+        .enabled(true)
+        .flexible()
+        .with_withdrawal_fee(WithdrawalFee::Fix(100));
+    let jars = vec![Jar::generate(1, &alice, &product.id).principal(1_000_000)];
+
+    let mut context = Context::new(admin);
+    write_state_v1(&product, jars, 1);
+
+    context.contract = Contract::migrate_state();
+
+    let migrated_product = context.contract.get_product(&product.id);
+    assert_eq!(product.terms, migrated_product.terms);
+    assert_eq!(None, migrated_product.get_unbonding_period());
+    assert_eq!(Some(WithdrawalFee::Fix(100)), migrated_product.withdrawal_fee);
+    assert_eq!(product.public_key, migrated_product.public_key);
+    assert!(migrated_product.is_enabled);
+}
+
+#[test]
+fn migrated_account_storage_is_sponsored() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("flexible_product")
+        .enabled(true)
+        .flexible()
+        .cap(0, 10_000_000);
+    let jars = vec![Jar::generate(1, &alice, &product.id).principal(1_000_000)];
+
+    let mut context = Context::new(admin.clone());
+    write_state_v1(&product, jars, 1);
+
+    context.contract = Contract::migrate_state();
+
+    context.switch_account(&admin);
+    context.contract.migrate_accounts(vec![alice.clone()]);
+
+    let balance = context.contract.storage_balance_of(alice.clone()).unwrap();
+    assert_eq!(0, balance.available.0);
+
+    let principal = context.contract.top_up(&alice, 1, U128(1_000));
+    assert_eq!(1_001_000, principal.0);
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn migrate_accounts_by_not_admin() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let mut context = Context::new(admin);
+
+    context.switch_account(&alice);
+    context.contract.migrate_accounts(vec![alice]);
+}
+
+#[test]
+#[should_panic(expected = "Jar with id: 2 is locked, migrate the account after its operation is completed")]
+fn migrate_account_with_locked_jar() {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("product").enabled(true);
+    let mut locked_jar = Jar::generate(2, &alice, &product.id).principal(2_000_000);
+    locked_jar.is_pending_withdraw = true;
+    let jars = vec![Jar::generate(1, &alice, &product.id).principal(1_000_000), locked_jar];
+
+    let mut context = Context::new(admin.clone());
+    write_state_v1(&product, jars, 2);
+
+    context.contract = Contract::migrate_state();
+
+    context.switch_account(&admin);
+    context.contract.migrate_accounts(vec![alice]);
+}
@@ -0,0 +1,210 @@
+use near_sdk::{assert_one_yocto, env, json_types::U64, near_bindgen, require, AccountId};
+
+use crate::{
+    event::{emit, ApproveOperatorData, EventKind, RevokeOperatorData},
+    operator::{
+        model::{Operator, OperatorAction},
+        view::OperatorView,
+    },
+    Contract, ContractExt,
+};
+
+/// The maximum number of active operators an account can approve.
+pub const MAX_OPERATORS: usize = 10;
+
+/// The `OperatorApi` trait defines methods for managing accounts authorized to claim, restake and withdraw
+/// on behalf of jar owners.
+pub trait OperatorApi {
+    /// Approves an account as an operator of the caller's jars. If the operator is already approved,
+    /// its actions and expiration date are replaced with the new ones.
+    /// The approval is charged to the caller's storage balance.
+    /// This method requires an attached deposit of exactly 1 yoctoNEAR.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator_id` - The `AccountId` of the operator.
+    /// * `actions` - An optional list of actions the operator is allowed to perform.
+    ///               If not provided, the operator is allowed to perform all the actions.
+    /// * `expires_at` - An optional expiration date of the approval, measured in milliseconds since the Unix epoch.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the caller approves themselves.
+    /// - If the provided list of actions is empty.
+    /// - If the expiration date is in the past.
+    /// - If the caller already has `MAX_OPERATORS` active operators and the operator isn't one of them.
+    /// - If the caller isn't registered for storage or its storage balance doesn't cover the approval.
+    fn approve_operator(
+        &mut self,
+        operator_id: AccountId,
+        actions: Option<Vec<OperatorAction>>,
+        expires_at: Option<U64>,
+    );
+
+    /// Revokes the approval of an operator of the caller's jars and releases the storage it used.
+    /// This method requires an attached deposit of exactly 1 yoctoNEAR.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator_id` - The `AccountId` of the operator.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the operator isn't approved by the caller.
+    fn revoke_operator(&mut self, operator_id: AccountId);
+
+    /// Retrieves active operators approved by the specified account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jar owner.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<OperatorView>` containing operators which approval hasn't expired yet.
+    fn get_operators(&self, account_id: AccountId) -> Vec<OperatorView>;
+}
+
+#[near_bindgen]
+impl OperatorApi for Contract {
+    #[payable]
+    fn approve_operator(
+        &mut self,
+        operator_id: AccountId,
+        actions: Option<Vec<OperatorAction>>,
+        expires_at: Option<U64>,
+    ) {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let now = env::block_timestamp_ms();
+
+        require!(operator_id != account_id, "Can't approve yourself as an operator");
+        require!(
+            actions.as_ref().map_or(true, |actions| !actions.is_empty()),
+            "Operator must be allowed to perform at least one action"
+        );
+        require!(
+            expires_at.map_or(true, |expires_at| expires_at.0 > now),
+            "Expiration date must be in the future"
+        );
+
+        let actions = actions.map(|actions| {
+            actions.into_iter().fold(vec![], |mut unique, action| {
+                if !unique.contains(&action) {
+                    unique.push(action);
+                }
+                unique
+            })
+        });
+
+        let operator = Operator {
+            account_id: operator_id.clone(),
+            actions: actions.clone(),
+            expires_at: expires_at.map(|expires_at| expires_at.0),
+        };
+
+        let initial_storage_usage = self.flush_storage_usage();
+
+        let operators = self.operators.entry(account_id.clone()).or_default();
+        operators.retain(|existing| existing.account_id != operator_id && existing.is_active(now));
+
+        require!(
+            operators.len() < MAX_OPERATORS,
+            format!("An account can't have more than {MAX_OPERATORS} operators")
+        );
+
+        operators.push(operator);
+
+        self.charge_storage(&account_id, initial_storage_usage);
+
+        emit(EventKind::ApproveOperator(ApproveOperatorData {
+            account_id,
+            operator_id,
+            actions,
+            expires_at,
+        }));
+    }
+
+    #[payable]
+    fn revoke_operator(&mut self, operator_id: AccountId) {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = self.flush_storage_usage();
+
+        let operators = self
+            .operators
+            .get_mut(&account_id)
+            .unwrap_or_else(|| env::panic_str(&format!("Operator '{operator_id}' isn't approved")));
+
+        let count_before = operators.len();
+        operators.retain(|operator| operator.account_id != operator_id);
+
+        require!(
+            operators.len() < count_before,
+            format!("Operator '{operator_id}' isn't approved")
+        );
+
+        if operators.is_empty() {
+            self.operators.remove(&account_id);
+        }
+
+        self.release_storage(&account_id, initial_storage_usage);
+
+        emit(EventKind::RevokeOperator(RevokeOperatorData {
+            account_id,
+            operator_id,
+        }));
+    }
+
+    fn get_operators(&self, account_id: AccountId) -> Vec<OperatorView> {
+        let now = env::block_timestamp_ms();
+
+        self.operators.get(&account_id).map_or_else(Vec::new, |operators| {
+            operators
+                .iter()
+                .filter(|operator| operator.is_active(now))
+                .map(Into::into)
+                .collect()
+        })
+    }
+}
+
+impl Contract {
+    /// Resolves the owner of jars an action is performed on.
+    ///
+    /// If `account_id` is not provided or matches the caller, the caller is the owner. Otherwise the caller
+    /// must be an active operator of `account_id` allowed to perform the `action`.
+    pub(crate) fn get_account_for_action(&self, account_id: Option<AccountId>, action: OperatorAction) -> AccountId {
+        let predecessor_id = env::predecessor_account_id();
+
+        let Some(account_id) = account_id else {
+            return predecessor_id;
+        };
+
+        if account_id == predecessor_id {
+            return account_id;
+        }
+
+        let now = env::block_timestamp_ms();
+        let operator = self
+            .operators
+            .get(&account_id)
+            .and_then(|operators| operators.iter().find(|operator| operator.account_id == predecessor_id))
+            .filter(|operator| operator.is_active(now))
+            .unwrap_or_else(|| {
+                env::panic_str(&format!(
+                    "Account '{predecessor_id}' isn't an operator of '{account_id}'"
+                ))
+            });
+
+        require!(
+            operator.allows(action),
+            format!("Operator '{predecessor_id}' isn't allowed to perform this action")
+        );
+
+        account_id
+    }
+}
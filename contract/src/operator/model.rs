@@ -0,0 +1,45 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+use crate::common::Timestamp;
+
+/// The `OperatorAction` enum describes actions which an operator can perform on behalf of a jar owner.
+/// Any tokens paid out as a result of these actions are always transferred to the owner of the jars.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "kebab-case")]
+pub enum OperatorAction {
+    /// Claiming interest from jars.
+    Claim,
+
+    /// Restaking mature jars.
+    Restake,
+
+    /// Withdrawing principal from jars to the owner's account.
+    WithdrawToSelf,
+}
+
+/// The `Operator` struct describes an account approved by a jar owner to manage their jars.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct Operator {
+    /// The account ID of the operator.
+    pub account_id: AccountId,
+
+    /// Actions the operator is allowed to perform. `None` means that all the actions are allowed.
+    pub actions: Option<Vec<OperatorAction>>,
+
+    /// An optional expiration date of the approval, measured in milliseconds since the Unix epoch.
+    pub expires_at: Option<Timestamp>,
+}
+
+impl Operator {
+    pub(crate) fn is_active(&self, now: Timestamp) -> bool {
+        self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+
+    pub(crate) fn allows(&self, action: OperatorAction) -> bool {
+        self.actions.as_ref().map_or(true, |actions| actions.contains(&action))
+    }
+}
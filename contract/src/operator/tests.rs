@@ -0,0 +1,227 @@
+#![cfg(test)]
+
+use model::U32;
+use near_contract_standards::storage_management::StorageManagement;
+use near_sdk::{
+    json_types::{U128, U64},
+    test_utils::accounts,
+    AccountId, PromiseOrValue,
+};
+
+use crate::{
+    claim::api::ClaimApi,
+    common::{tests::Context, udecimal::UDecimal, MS_IN_YEAR},
+    jar::{api::JarApi, model::Jar},
+    operator::{
+        api::{OperatorApi, MAX_OPERATORS},
+        model::OperatorAction,
+    },
+    product::model::{Apy, Product},
+    withdraw::api::WithdrawApi,
+};
+
+fn prepare_context() -> (AccountId, AccountId, Jar, Product, Context) {
+    let alice = accounts(0);
+    let bob = accounts(1);
+    let admin = accounts(2);
+
+    let product = Product::generate("product")
+        .apy(Apy::Constant(UDecimal::new(12, 2)))
+        .lockup_term(MS_IN_YEAR)
+        .with_allows_restaking(true);
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let context = Context::new(admin)
+        .with_products(&[product.clone()])
+        .with_jars(&[jar.clone()])
+        .with_storage_deposit(&[alice.clone()]);
+
+    (alice, bob, jar, product, context)
+}
+
+fn approve_operator(
+    context: &mut Context,
+    account_id: &AccountId,
+    operator_id: &AccountId,
+    actions: Option<Vec<OperatorAction>>,
+    expires_at: Option<u64>,
+) {
+    context.switch_account(account_id);
+    context.with_deposit_yocto(1, |context| {
+        context
+            .contract
+            .approve_operator(operator_id.clone(), actions, expires_at.map(U64));
+    });
+    context.switch_account(operator_id);
+}
+
+#[test]
+fn approve_and_revoke_operator() {
+    let (alice, bob, _, _, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, Some(vec![OperatorAction::Claim]), None);
+
+    let operators = context.contract.get_operators(alice.clone());
+    assert_eq!(1, operators.len());
+    assert_eq!(bob, operators[0].operator_id);
+    assert_eq!(Some(vec![OperatorAction::Claim]), operators[0].actions);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.revoke_operator(bob.clone()));
+
+    assert!(context.contract.get_operators(alice).is_empty());
+}
+
+#[test]
+fn approval_is_charged_to_owner_storage() {
+    let (alice, bob, _, _, mut context) = prepare_context();
+
+    let initial_balance = context.contract.storage_balance_of(alice.clone()).unwrap();
+
+    approve_operator(&mut context, &alice, &bob, None, None);
+
+    let balance = context.contract.storage_balance_of(alice.clone()).unwrap();
+    assert!(balance.available.0 < initial_balance.available.0);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.revoke_operator(bob.clone()));
+
+    let balance = context.contract.storage_balance_of(alice).unwrap();
+    assert_eq!(initial_balance.available, balance.available);
+}
+
+#[test]
+#[should_panic(expected = "Account 'danny' isn't registered for storage")]
+fn approve_operator_by_not_registered_account() {
+    let (_, bob, _, _, mut context) = prepare_context();
+
+    approve_operator(&mut context, &accounts(3), &bob, None, None);
+}
+
+#[test]
+fn operator_claims_to_owner() {
+    let (alice, bob, jar, _, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, Some(vec![OperatorAction::Claim]), None);
+
+    context.set_block_timestamp_in_days(365);
+
+    let PromiseOrValue::Value(claimed) =
+        context
+            .contract
+            .claim_jars(vec![U32(jar.id)], None, Some(alice.clone()), None, None)
+    else {
+        panic!();
+    };
+
+    assert_eq!(U128(120_000), claimed);
+    assert_eq!(
+        120_000,
+        context.contract.get_jar_internal(&alice, jar.id).claimed_balance
+    );
+}
+
+#[test]
+fn operator_restakes_for_owner() {
+    let (alice, bob, jar, product, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, None, None);
+
+    context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
+
+    let restaked = context.contract.restake(U32(jar.id), Some(alice.clone()));
+
+    assert_eq!(alice, restaked.account_id);
+    assert!(context.contract.get_jars_for_account(bob).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Account 'bob' isn't an operator of 'alice'")]
+fn claim_by_not_approved_operator() {
+    let (alice, bob, jar, _, mut context) = prepare_context();
+
+    context.switch_account(&bob);
+    context
+        .contract
+        .claim_jars(vec![U32(jar.id)], None, Some(alice), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Operator 'bob' isn't allowed to perform this action")]
+fn withdraw_by_operator_allowed_only_to_claim() {
+    let (alice, bob, jar, product, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, Some(vec![OperatorAction::Claim]), None);
+
+    context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
+    context.contract.withdraw(U32(jar.id), None, Some(alice), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Only the owner can choose the receiver")]
+fn operator_claims_to_another_receiver() {
+    let (alice, bob, jar, _, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, Some(vec![OperatorAction::Claim]), None);
+
+    context.set_block_timestamp_in_days(365);
+    context
+        .contract
+        .claim_jars(vec![U32(jar.id)], None, Some(alice), Some(bob), None);
+}
+
+#[test]
+#[should_panic(expected = "Account 'bob' isn't an operator of 'alice'")]
+fn claim_by_expired_operator() {
+    let (alice, bob, jar, _, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, None, Some(MS_IN_YEAR));
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR);
+    context
+        .contract
+        .claim_jars(vec![U32(jar.id)], None, Some(alice), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Operator must be allowed to perform at least one action")]
+fn approve_operator_without_actions() {
+    let (alice, bob, _, _, mut context) = prepare_context();
+
+    approve_operator(&mut context, &alice, &bob, Some(vec![]), None);
+}
+
+#[test]
+#[should_panic(expected = "An account can't have more than 10 operators")]
+fn approve_too_many_operators() {
+    let (alice, _, _, _, mut context) = prepare_context();
+
+    for i in 0..=MAX_OPERATORS {
+        let operator_id: AccountId = format!("operator_{i}").parse().unwrap();
+        approve_operator(&mut context, &alice, &operator_id, None, None);
+    }
+}
+
+#[test]
+fn expired_operators_free_slots() {
+    let (alice, _, _, _, mut context) = prepare_context();
+
+    for i in 0..MAX_OPERATORS {
+        let operator_id: AccountId = format!("operator_{i}").parse().unwrap();
+        approve_operator(&mut context, &alice, &operator_id, None, Some(1_000));
+    }
+
+    context.set_block_timestamp_in_ms(1_000);
+
+    let bob = accounts(1);
+    approve_operator(
+        &mut context,
+        &alice,
+        &bob,
+        Some(vec![OperatorAction::Claim, OperatorAction::Claim]),
+        None,
+    );
+
+    let operators = context.contract.get_operators(alice);
+    assert_eq!(1, operators.len());
+    assert_eq!(Some(vec![OperatorAction::Claim]), operators[0].actions);
+}
@@ -0,0 +1,25 @@
+use near_sdk::{
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+use crate::operator::model::{Operator, OperatorAction};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorView {
+    pub operator_id: AccountId,
+    pub actions: Option<Vec<OperatorAction>>,
+    pub expires_at: Option<U64>,
+}
+
+impl From<&Operator> for OperatorView {
+    fn from(value: &Operator) -> Self {
+        Self {
+            operator_id: value.account_id.clone(),
+            actions: value.actions.clone(),
+            expires_at: value.expires_at.map(U64),
+        }
+    }
+}
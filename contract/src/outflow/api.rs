@@ -0,0 +1,167 @@
+use model::{ProductId, TokenAmount};
+use near_sdk::{assert_one_yocto, env, json_types::U128, near_bindgen, require};
+
+use crate::{
+    assert::require_ok,
+    common::Timestamp,
+    event::{emit, EventKind, SetOutflowLimitData},
+    outflow::{
+        model::OutflowLimit,
+        view::{OutflowAllowanceView, OutflowLimitDto},
+    },
+    Contract, ContractExt,
+};
+
+/// The `OutflowApi` trait defines methods for limiting principal withdrawn from products.
+pub trait OutflowApi {
+    /// Sets a limit on principal withdrawn from a product within a sliding time window, or removes the limit.
+    /// Replacing a limit keeps the withdrawals within the window, so the limit can be raised in emergencies
+    /// without resetting the amount withdrawn within it.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - The ID of the product.
+    /// * `limit` - An optional `OutflowLimitDto` with the share of the product TVL which can be withdrawn
+    ///             within a window and the window duration. `None` removes the limit.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager, if the product doesn't exist,
+    /// if the window duration is zero or if the share of the TVL exceeds 1.
+    fn set_outflow_limit(&mut self, product_id: ProductId, limit: Option<OutflowLimitDto>);
+
+    /// Returns the TVL of a product, the amount of principal which can be withdrawn from it at the moment
+    /// and when the allowance grows next.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the product doesn't exist.
+    fn get_outflow_allowance(&self, product_id: ProductId) -> OutflowAllowanceView;
+}
+
+#[near_bindgen]
+impl OutflowApi for Contract {
+    #[payable]
+    fn set_outflow_limit(&mut self, product_id: ProductId, limit: Option<OutflowLimitDto>) {
+        self.assert_manager();
+        assert_one_yocto();
+
+        self.get_product(&product_id);
+
+        let limit: Option<OutflowLimit> = limit.map(|limit| {
+            require!(limit.window.0 > 0, "Outflow window must be positive");
+
+            let (U128(significand), exponent) = limit.percent;
+            require!(
+                10u128.checked_pow(exponent).is_some_and(|one| significand <= one),
+                "Outflow limit percent can't exceed 1"
+            );
+
+            let limit: OutflowLimit = limit.into();
+
+            match self.outflow_limits.get(&product_id) {
+                Some(current) => OutflowLimit {
+                    percent: limit.percent,
+                    window: limit.window,
+                    ..current.clone()
+                },
+                None => limit,
+            }
+        });
+
+        emit(EventKind::SetOutflowLimit(SetOutflowLimitData {
+            product_id: product_id.clone(),
+            limit: limit.as_ref().map(Into::into),
+        }));
+
+        match limit {
+            Some(limit) => self.outflow_limits.insert(product_id, limit),
+            None => self.outflow_limits.remove(&product_id),
+        };
+    }
+
+    fn get_outflow_allowance(&self, product_id: ProductId) -> OutflowAllowanceView {
+        self.get_product(&product_id);
+
+        let tvl = self.get_product_tvl(&product_id);
+        let limit = self
+            .outflow_limits
+            .get(&product_id)
+            .map(|limit| limit.current(env::block_timestamp_ms()));
+
+        OutflowAllowanceView {
+            tvl: U128(tvl),
+            limit: limit.as_ref().map(Into::into),
+            allowance: limit.as_ref().map(|limit| U128(limit.allowance(tvl))),
+            next_release_at: limit.as_ref().and_then(OutflowLimit::next_release_at).map(Into::into),
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn get_product_tvl(&self, product_id: &ProductId) -> TokenAmount {
+        self.product_tvls.get(product_id).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn increase_product_tvl(&mut self, product_id: &ProductId, amount: TokenAmount) {
+        if amount > 0 {
+            *self.product_tvls.entry(product_id.clone()).or_default() += amount;
+        }
+    }
+
+    pub(crate) fn decrease_product_tvl(&mut self, product_id: &ProductId, amount: TokenAmount) {
+        if let Some(tvl) = self.product_tvls.get_mut(product_id) {
+            *tvl = tvl.saturating_sub(amount);
+        }
+    }
+
+    /// Checks whether `amount` of principal can be withdrawn from a product without exceeding its outflow limit.
+    pub(crate) fn check_outflow(
+        &self,
+        product_id: &ProductId,
+        amount: TokenAmount,
+        now: Timestamp,
+    ) -> Result<(), String> {
+        let Some(limit) = self.outflow_limits.get(product_id) else {
+            return Ok(());
+        };
+
+        let limit = limit.current(now);
+
+        if amount > limit.allowance(self.get_product_tvl(product_id)) {
+            return Err(match limit.next_release_at() {
+                Some(release_at) => {
+                    format!("Withdrawal rate limit of product {product_id} is exceeded, retry after {release_at}")
+                }
+                None => format!("Withdrawal rate limit of product {product_id} is exceeded"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Counts `amount` of principal as withdrawn from a product at `now`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the amount exceeds the outflow allowance of the product.
+    pub(crate) fn consume_outflow(&mut self, product_id: &ProductId, amount: TokenAmount, now: Timestamp) {
+        require_ok(self.check_outflow(product_id, amount, now));
+
+        if amount == 0 {
+            return;
+        }
+
+        if let Some(limit) = self.outflow_limits.get_mut(product_id) {
+            *limit = limit.current(now);
+            limit.consume(amount, now);
+        }
+    }
+
+    /// Returns `amount` of principal to the allowance of a product, e.g. when a withdrawal transfer failed.
+    pub(crate) fn release_outflow(&mut self, product_id: &ProductId, amount: TokenAmount) {
+        if let Some(limit) = self.outflow_limits.get_mut(product_id) {
+            limit.release(amount);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::cmp;
+
+use model::TokenAmount;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+use crate::common::{udecimal::UDecimal, Duration, Timestamp};
+
+/// The number of buckets a window is split into. Withdrawals are counted within one more bucket than the window
+/// contains, so any period of the window duration is covered and the limit can't be exceeded across two windows.
+pub const OUTFLOW_WINDOW_BUCKETS: u64 = 10;
+
+/// The `OutflowLimit` struct describes a limit on principal withdrawn from a product within a sliding time window.
+/// The limit is calculated from the product TVL increased by the principal withdrawn within the window.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct OutflowLimit {
+    /// The share of the product TVL which can be withdrawn within a window.
+    pub percent: UDecimal,
+
+    /// The duration of a window in milliseconds.
+    pub window: Duration,
+
+    /// Principal withdrawn within the window, grouped by buckets. Each element contains the timestamp
+    /// of when a bucket started and the amount withdrawn within it, from the oldest to the newest.
+    pub withdrawals: Vec<(Timestamp, TokenAmount)>,
+}
+
+impl OutflowLimit {
+    pub(crate) fn new(percent: UDecimal, window: Duration) -> Self {
+        Self {
+            percent,
+            window,
+            withdrawals: vec![],
+        }
+    }
+
+    fn bucket_duration(&self) -> Duration {
+        self.window.div_ceil(OUTFLOW_WINDOW_BUCKETS)
+    }
+
+    fn bucket_started_at(&self, now: Timestamp) -> Timestamp {
+        now - now % self.bucket_duration()
+    }
+
+    /// Returns the limit without withdrawals which are out of the window at `now`.
+    pub(crate) fn current(&self, now: Timestamp) -> Self {
+        let window_started_at = self
+            .bucket_started_at(now)
+            .saturating_sub(OUTFLOW_WINDOW_BUCKETS * self.bucket_duration());
+
+        Self {
+            withdrawals: self
+                .withdrawals
+                .iter()
+                .filter(|(started_at, _)| *started_at >= window_started_at)
+                .copied()
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the amount of principal withdrawn within the window.
+    pub(crate) fn withdrawn(&self) -> TokenAmount {
+        self.withdrawals.iter().map(|(_, amount)| amount).sum()
+    }
+
+    /// Returns the amount of principal which can be withdrawn within the window.
+    pub(crate) fn allowance(&self, tvl: TokenAmount) -> TokenAmount {
+        let withdrawn = self.withdrawn();
+
+        (&self.percent * (tvl + withdrawn)).saturating_sub(withdrawn)
+    }
+
+    /// Returns when the oldest withdrawal leaves the window and the allowance grows, if there are any withdrawals.
+    pub(crate) fn next_release_at(&self) -> Option<Timestamp> {
+        self.withdrawals
+            .first()
+            .map(|(started_at, _)| started_at + (OUTFLOW_WINDOW_BUCKETS + 1) * self.bucket_duration())
+    }
+
+    /// Counts `amount` of principal as withdrawn at `now`.
+    pub(crate) fn consume(&mut self, amount: TokenAmount, now: Timestamp) {
+        let bucket_started_at = self.bucket_started_at(now);
+
+        match self.withdrawals.last_mut() {
+            Some((started_at, withdrawn)) if *started_at == bucket_started_at => *withdrawn += amount,
+            _ => self.withdrawals.push((bucket_started_at, amount)),
+        }
+    }
+
+    /// Returns `amount` of principal to the allowance, starting from the newest withdrawals.
+    pub(crate) fn release(&mut self, amount: TokenAmount) {
+        let mut remaining = amount;
+
+        for (_, withdrawn) in self.withdrawals.iter_mut().rev() {
+            let released = cmp::min(remaining, *withdrawn);
+            *withdrawn -= released;
+            remaining -= released;
+
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        self.withdrawals.retain(|(_, withdrawn)| *withdrawn > 0);
+    }
+}
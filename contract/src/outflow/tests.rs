@@ -0,0 +1,233 @@
+#![cfg(test)]
+
+use model::U32;
+use near_sdk::{
+    json_types::{U128, U64},
+    test_utils::accounts,
+    AccountId, PromiseOrValue,
+};
+
+use crate::{
+    common::{tests::Context, udecimal::UDecimal, MS_IN_MINUTE},
+    jar::{api::JarApi, model::JarTicket},
+    outflow::{api::OutflowApi, model::OUTFLOW_WINDOW_BUCKETS, view::OutflowLimitDto},
+    product::model::{Apy, Product},
+    withdraw::api::WithdrawApi,
+};
+
+const MS_IN_DAY: u64 = 24 * 60 * MS_IN_MINUTE;
+const BUCKET: u64 = MS_IN_DAY / OUTFLOW_WINDOW_BUCKETS;
+
+fn prepare_context() -> (AccountId, AccountId, Product, Context) {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("flexible_product")
+        .enabled(true)
+        .flexible()
+        .apy(Apy::Constant(UDecimal::new(12, 2)))
+        .cap(0, 1_000_000);
+    let mut context = Context::new(admin.clone())
+        .with_products(&[product.clone()])
+        .with_storage_deposit(&[alice.clone()]);
+
+    for _ in 0..2 {
+        create_jar(&mut context, &alice, &product, 1_000_000);
+    }
+
+    (alice, admin, product, context)
+}
+
+fn create_jar(context: &mut Context, account_id: &AccountId, product: &Product, amount: u128) -> U32 {
+    let ticket = JarTicket {
+        product_id: product.id.clone(),
+        valid_until: 0.into(),
+    };
+
+    context
+        .contract
+        .create_jar(account_id.clone(), ticket, U128(amount), None)
+        .id
+}
+
+fn set_limit(context: &mut Context, admin: &AccountId, product: &Product, percent: u128) {
+    context.switch_account(admin);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_outflow_limit(
+            product.id.clone(),
+            Some(OutflowLimitDto {
+                percent: (U128(percent), 2),
+                window: U64(MS_IN_DAY),
+            }),
+        );
+    });
+}
+
+fn withdraw(context: &mut Context, account_id: &AccountId, jar_id: u32, amount: u128) -> U128 {
+    context.switch_account(account_id);
+
+    let PromiseOrValue::Value(withdrawn) = context
+        .contract
+        .withdraw(U32(jar_id), Some(U128(amount)), None, None, None)
+    else {
+        panic!();
+    };
+
+    withdrawn.withdrawn_amount
+}
+
+#[test]
+fn product_tvl_follows_deposits_and_withdrawals() {
+    let (alice, _, product, mut context) = prepare_context();
+
+    assert_eq!(
+        U128(2_000_000),
+        context.contract.get_outflow_allowance(product.id.clone()).tvl
+    );
+
+    withdraw(&mut context, &alice, 1, 300_000);
+
+    let allowance = context.contract.get_outflow_allowance(product.id);
+    assert_eq!(U128(1_700_000), allowance.tvl);
+    assert_eq!(None, allowance.limit);
+    assert_eq!(None, allowance.allowance);
+}
+
+#[test]
+fn withdraw_within_outflow_limit() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    set_limit(&mut context, &admin, &product, 5);
+    context.set_block_timestamp_in_ms(MS_IN_MINUTE);
+
+    assert_eq!(U128(60_000), withdraw(&mut context, &alice, 1, 60_000));
+
+    let allowance = context.contract.get_outflow_allowance(product.id);
+    assert_eq!(Some(U128(40_000)), allowance.allowance);
+    assert_eq!(Some(U64(MS_IN_DAY + BUCKET)), allowance.next_release_at);
+}
+
+#[test]
+#[should_panic(expected = "Withdrawal rate limit of product flexible_product is exceeded, retry after 95040000")]
+fn withdraw_over_outflow_limit() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    set_limit(&mut context, &admin, &product, 5);
+    context.set_block_timestamp_in_ms(MS_IN_MINUTE);
+
+    withdraw(&mut context, &alice, 1, 60_000);
+    withdraw(&mut context, &alice, 2, 60_000);
+}
+
+#[test]
+fn jar_over_outflow_allowance_is_not_withdrawable() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    assert!(context.contract.get_jar_details(alice.clone(), U32(1)).is_withdrawable);
+
+    set_limit(&mut context, &admin, &product, 5);
+    context.set_block_timestamp_in_ms(MS_IN_MINUTE);
+
+    assert!(!context.contract.get_jar_details(alice, U32(1)).is_withdrawable);
+}
+
+#[test]
+fn withdrawals_leave_sliding_window() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    set_limit(&mut context, &admin, &product, 5);
+
+    withdraw(&mut context, &alice, 1, 100_000);
+
+    context.set_block_timestamp_in_ms(MS_IN_DAY);
+
+    let allowance = context.contract.get_outflow_allowance(product.id.clone());
+    assert_eq!(Some(U128(0)), allowance.allowance);
+
+    context.set_block_timestamp_in_ms(MS_IN_DAY + BUCKET);
+
+    let allowance = context.contract.get_outflow_allowance(product.id);
+    assert_eq!(Some(U128(95_000)), allowance.allowance);
+    assert_eq!(None, allowance.next_release_at);
+
+    withdraw(&mut context, &alice, 2, 95_000);
+}
+
+#[test]
+#[should_panic(expected = "Withdrawal rate limit of product flexible_product is exceeded")]
+fn outflow_limit_is_not_doubled_across_windows() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    set_limit(&mut context, &admin, &product, 5);
+
+    context.set_block_timestamp_in_ms(MS_IN_DAY - 1);
+    withdraw(&mut context, &alice, 1, 100_000);
+
+    context.set_block_timestamp_in_ms(MS_IN_DAY + 1);
+    withdraw(&mut context, &alice, 2, 50_000);
+}
+
+#[test]
+fn manager_raises_outflow_limit() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    set_limit(&mut context, &admin, &product, 5);
+    withdraw(&mut context, &alice, 1, 100_000);
+
+    set_limit(&mut context, &admin, &product, 10);
+
+    let allowance = context.contract.get_outflow_allowance(product.id.clone());
+    assert_eq!(Some(U128(100_000)), allowance.allowance);
+    assert_eq!(Some(U64(MS_IN_DAY + BUCKET)), allowance.next_release_at);
+
+    withdraw(&mut context, &alice, 2, 100_000);
+
+    context.switch_account(&admin);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_outflow_limit(product.id.clone(), None);
+    });
+
+    withdraw(&mut context, &alice, 2, 500_000);
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn set_outflow_limit_by_not_manager() {
+    let (alice, _, product, mut context) = prepare_context();
+
+    set_limit(&mut context, &alice, &product, 5);
+}
+
+#[test]
+#[should_panic(expected = "Outflow window must be positive")]
+fn set_outflow_limit_with_zero_window() {
+    let (_, admin, product, mut context) = prepare_context();
+
+    context.switch_account(&admin);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_outflow_limit(
+            product.id.clone(),
+            Some(OutflowLimitDto {
+                percent: (U128(5), 2),
+                window: U64(0),
+            }),
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "Outflow limit percent can't exceed 1")]
+fn set_outflow_limit_over_whole_tvl() {
+    let (_, admin, product, mut context) = prepare_context();
+
+    context.switch_account(&admin);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_outflow_limit(
+            product.id.clone(),
+            Some(OutflowLimitDto {
+                percent: (U128(101), 2),
+                window: U64(MS_IN_DAY),
+            }),
+        );
+    });
+}
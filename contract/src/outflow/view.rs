@@ -0,0 +1,50 @@
+use near_sdk::{
+    json_types::{U128, U64},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::{common::udecimal::UDecimal, outflow::model::OutflowLimit};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OutflowLimitDto {
+    /// Decimal representation of the share of the product TVL, where the first element is significand
+    /// as a string and the second one is exponent as an integer. I.e. "0.05" becomes ("5", 2): 5 * 10^-2.
+    pub percent: (U128, u32),
+
+    /// The duration of a window in milliseconds.
+    pub window: U64,
+}
+
+impl From<OutflowLimitDto> for OutflowLimit {
+    fn from(value: OutflowLimitDto) -> Self {
+        OutflowLimit::new(UDecimal::new(value.percent.0 .0, value.percent.1), value.window.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OutflowLimitView {
+    pub percent: f32,
+    pub window: U64,
+}
+
+impl From<&OutflowLimit> for OutflowLimitView {
+    fn from(value: &OutflowLimit) -> Self {
+        Self {
+            percent: value.percent.to_f32(),
+            window: U64(value.window),
+        }
+    }
+}
+
+/// The outflow state of a product. `allowance` is absent if the product has no limit. `next_release_at` is
+/// the timestamp of when the oldest withdrawal within the window stops counting, it's absent if there are none.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OutflowAllowanceView {
+    pub tvl: U128,
+    pub limit: Option<OutflowLimitView>,
+    pub allowance: Option<U128>,
+    pub next_release_at: Option<U64>,
+}
@@ -1,15 +1,26 @@
-use model::jar::JarIdView;
-use near_sdk::{env, near_bindgen, AccountId};
+use std::cmp;
+
+use model::{
+    jar::{JarId, JarIdView},
+    ProductId,
+};
+use near_sdk::{
+    assert_one_yocto, env,
+    json_types::{Base64VecU8, U64},
+    near_bindgen, require, AccountId,
+};
 
-use crate::event::BatchPenaltyData;
 use crate::{
+    common::Timestamp,
     event::{
-        emit,
-        EventKind::{ApplyPenalty, BatchApplyPenalty},
-        PenaltyData,
+        emit, BatchPenaltyData,
+        EventKind::{ApplyPenalty, BatchApplyPenalty, SetMaxPenaltyLookback, SetPenaltyKey},
+        PenaltyData, SetMaxPenaltyLookbackData, SetPenaltyKeyData,
     },
+    history::model::JarActivityKind,
+    penalty::{model::PenaltyStatement, view::PenaltyIntervalView},
     product::model::Apy,
-    Contract, ContractExt, JarsStorage,
+    Contract, ContractExt,
 };
 
 /// The `PenaltyApi` trait provides methods for applying or canceling penalties on premium jars within the smart contract.
@@ -19,17 +30,26 @@ pub trait PenaltyApi {
     /// This method allows the contract manager to apply or cancel a penalty for a premium jar. Premium jars are those associated
     /// with products having Downgradable APY. When a user violates the terms of a premium product and a penalty is applied, the
     /// interest for the jar is calculated using a downgraded APY rate. If the terms are no longer violated, the penalty can be canceled.
+    /// Setting the current penalty status again doesn't change the jar.
+    /// A backdated change recalculates the interest accrued since `effective_at`. If the interest
+    /// has been claimed since then, only the unclaimed interest is taken back.
     ///
     /// # Arguments
     ///
     /// * `account_id` - The account of user which owns this jar.
     /// * `jar_id` - The ID of the jar for which the penalty status is being modified.
     /// * `value` - A boolean value indicating whether the penalty should be applied (`true`) or canceled (`false`).
+    /// * `effective_at` - An optional timestamp in milliseconds the change is effective since. Defaults to the current
+    ///                    block time. It can be in the past within the maximum penalty lookback.
     ///
     /// # Panics
     ///
-    /// This method will panic if the jar's associated product has a constant APY rather than a downgradable APY.
-    fn set_penalty(&mut self, account_id: AccountId, jar_id: JarIdView, value: bool);
+    /// This method will panic under the following conditions:
+    /// - If it's called by an account other than the manager.
+    /// - If the jar's associated product has a constant APY rather than a downgradable APY.
+    /// - If `effective_at` is in the future or earlier than the maximum penalty lookback allows.
+    /// - If `effective_at` is earlier than the creation or the last penalty change of the jar.
+    fn set_penalty(&mut self, account_id: AccountId, jar_id: JarIdView, value: bool, effective_at: Option<U64>);
 
     /// Batched version of `set_penalty`
     ///
@@ -37,70 +57,295 @@ pub trait PenaltyApi {
     ///
     /// * `jars` - List of Account IDs and their Jar IDs to which penalty must be applied.
     /// * `value` - A boolean value indicating whether the penalty should be applied (`true`) or canceled (`false`).
+    /// * `effective_at` - An optional timestamp in milliseconds the change is effective since.
+    ///                    Defaults to the current block time.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic under the same conditions as `set_penalty` for any of the jars.
+    fn batch_set_penalty(&mut self, jars: Vec<(AccountId, Vec<JarIdView>)>, value: bool, effective_at: Option<U64>);
+
+    /// Sets the maximum duration a penalty can be backdated by.
+    /// This method requires an attached deposit of exactly 1 yoctoNEAR.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookback` - The duration in milliseconds. Zero disables backdating.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager.
+    fn set_max_penalty_lookback(&mut self, lookback: U64);
+
+    /// Returns the maximum duration in milliseconds a penalty can be backdated by.
+    fn get_max_penalty_lookback(&self) -> U64;
+
+    /// Returns up to `MAX_PENALTY_INTERVALS` latest periods when a penalty was applied to a jar,
+    /// from the oldest to the newest.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the jar doesn't exist.
+    fn get_penalty_history(&self, account_id: AccountId, jar_id: JarIdView) -> Vec<PenaltyIntervalView>;
+
+    /// Sets a public key verifying penalty statements of a product, so the penalty oracle doesn't need
+    /// the manager key. Without a penalty key statements are verified with the public key of the product.
+    /// This method requires an attached deposit of exactly 1 yoctoNEAR.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - The ID of the product.
+    /// * `public_key` - An optional Ed25519 public key represented as a base64-encoded byte array.
+    ///                  `None` removes the penalty key.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager or if the product doesn't exist.
+    fn set_penalty_key(&mut self, product_id: ProductId, public_key: Option<Base64VecU8>);
+
+    /// Applies or cancels a penalty according to a statement signed by the penalty oracle of a product.
+    /// It can be called by any account, e.g. a relayer.
+    ///
+    /// # Arguments
+    ///
+    /// * `statement` - A `PenaltyStatement` describing the jars and the penalty change.
+    /// * `signature` - An Ed25519 signature of the SHA-256 hash of the statement signature material,
+    ///                 represented as a base64-encoded byte array. The signature material is the Borsh
+    ///                 serialization of the `"penalty"` domain, the contract account and the statement fields.
     ///
     /// # Panics
     ///
-    /// This method will panic if the jar's associated product has a constant APY rather than a downgradable APY.
-    fn batch_set_penalty(&mut self, jars: Vec<(AccountId, Vec<JarIdView>)>, value: bool);
+    /// This method will panic under the following conditions:
+    /// - If the product has neither a penalty key nor a public key, or the signature doesn't match.
+    /// - If the nonce of the statement isn't greater than the nonce of the last statement applied to the account
+    ///   within the product.
+    /// - If any of the jars doesn't belong to the product.
+    /// - Under the same conditions as `set_penalty` regarding the APY and `effective_at`. If the statement
+    ///   applies to all jars of the account, jars created or changed after `effective_at` are penalized
+    ///   since their creation or last penalty change instead.
+    fn apply_signed_penalty(&mut self, statement: PenaltyStatement, signature: Base64VecU8);
+
+    /// Returns the nonce of the last penalty statement applied to an account within a product.
+    /// It's zero if there were none.
+    fn get_penalty_nonce(&self, product_id: ProductId, account_id: AccountId) -> U64;
 }
 
 #[near_bindgen]
 impl PenaltyApi for Contract {
-    fn set_penalty(&mut self, account_id: AccountId, jar_id: JarIdView, value: bool) {
+    fn set_penalty(&mut self, account_id: AccountId, jar_id: JarIdView, value: bool, effective_at: Option<U64>) {
         self.assert_manager();
 
-        let jar_id = jar_id.0;
-        let jar = self.get_jar_internal(&account_id, jar_id);
-        let product = self.get_product(&jar.product_id).clone();
-        let now = env::block_timestamp_ms();
+        let effective_at = self.get_penalty_effective_at(effective_at);
 
-        assert_penalty_apy(&product.apy);
-        self.get_jar_mut_internal(&account_id, jar_id)
-            .apply_penalty(&product, value, now);
+        self.set_penalty_internal(&account_id, jar_id.0, value, effective_at);
 
         emit(ApplyPenalty(PenaltyData {
-            id: jar_id,
+            id: jar_id.0,
             is_applied: value,
-            timestamp: now,
+            timestamp: effective_at,
         }));
     }
 
-    fn batch_set_penalty(&mut self, jars: Vec<(AccountId, Vec<JarIdView>)>, value: bool) {
+    fn batch_set_penalty(&mut self, jars: Vec<(AccountId, Vec<JarIdView>)>, value: bool, effective_at: Option<U64>) {
         self.assert_manager();
 
-        let mut applied_jars = vec![];
+        let effective_at = self.get_penalty_effective_at(effective_at);
 
-        let now = env::block_timestamp_ms();
+        let mut applied_jars = vec![];
 
         for (account_id, jars) in jars {
-            let account_jars = self
-                .account_jars
-                .get_mut(&account_id)
-                .unwrap_or_else(|| env::panic_str(&format!("Account '{account_id}' doesn't exist")));
-
             for jar_id in jars {
-                let jar_id = jar_id.0;
-
-                let jar = account_jars.get_jar_mut(jar_id);
-
-                let product = self
-                    .products
-                    .get(&jar.product_id)
-                    .unwrap_or_else(|| env::panic_str(&format!("Product '{}' doesn't exist", jar.product_id)));
+                self.set_penalty_internal(&account_id, jar_id.0, value, effective_at);
 
-                assert_penalty_apy(&product.apy);
-                jar.apply_penalty(product, value, now);
-
-                applied_jars.push(jar_id);
+                applied_jars.push(jar_id.0);
             }
         }
 
         emit(BatchApplyPenalty(BatchPenaltyData {
             jars: applied_jars,
             is_applied: value,
-            timestamp: now,
+            timestamp: effective_at,
+        }));
+    }
+
+    #[payable]
+    fn set_max_penalty_lookback(&mut self, lookback: U64) {
+        self.assert_manager();
+        assert_one_yocto();
+
+        self.max_penalty_lookback = lookback.0;
+
+        emit(SetMaxPenaltyLookback(SetMaxPenaltyLookbackData {
+            lookback: lookback.0,
         }));
     }
+
+    fn get_max_penalty_lookback(&self) -> U64 {
+        U64(self.max_penalty_lookback)
+    }
+
+    fn get_penalty_history(&self, account_id: AccountId, jar_id: JarIdView) -> Vec<PenaltyIntervalView> {
+        self.get_jar_internal(&account_id, jar_id.0)
+            .penalties
+            .iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    #[payable]
+    fn set_penalty_key(&mut self, product_id: ProductId, public_key: Option<Base64VecU8>) {
+        self.assert_manager();
+        assert_one_yocto();
+
+        self.get_product(&product_id);
+
+        match &public_key {
+            Some(public_key) => self.penalty_keys.insert(product_id.clone(), public_key.0.clone()),
+            None => self.penalty_keys.remove(&product_id),
+        };
+
+        emit(SetPenaltyKey(SetPenaltyKeyData {
+            product_id,
+            pk: public_key,
+        }));
+    }
+
+    fn apply_signed_penalty(&mut self, statement: PenaltyStatement, signature: Base64VecU8) {
+        let product = self.get_product(&statement.product_id);
+
+        let Some(public_key) = self
+            .penalty_keys
+            .get(&statement.product_id)
+            .or(product.public_key.as_ref())
+            .cloned()
+        else {
+            env::panic_str(&format!(
+                "Product {} has no key to verify penalties",
+                statement.product_id
+            ));
+        };
+
+        let hash = env::sha256(&statement.get_signature_material(&env::current_account_id()));
+        require!(
+            Self::verify_signature(&signature.0, &public_key, &hash),
+            "Not matching signature"
+        );
+
+        let account_id = statement.account_id;
+        let last_nonce = self
+            .get_penalty_nonce(statement.product_id.clone(), account_id.clone())
+            .0;
+
+        require!(
+            statement.nonce.0 > last_nonce,
+            format!("Penalty statement nonce must be greater than {last_nonce}")
+        );
+        self.penalty_nonces
+            .insert((statement.product_id.clone(), account_id.clone()), statement.nonce.0);
+
+        let effective_at = self.get_penalty_effective_at(Some(statement.effective_at));
+        let is_all_jars = statement.jar_ids.is_none();
+
+        let jar_ids: Vec<JarId> = match statement.jar_ids {
+            Some(jar_ids) => jar_ids.iter().map(|jar_id| jar_id.0).collect(),
+            None => self
+                .account_jar_ids(&account_id)
+                .into_iter()
+                .filter(|jar_id| self.get_stored_jar(*jar_id).product_id == statement.product_id)
+                .collect(),
+        };
+
+        for jar_id in &jar_ids {
+            let jar = self.get_jar_internal(&account_id, *jar_id);
+
+            require!(
+                jar.product_id == statement.product_id,
+                format!(
+                    "Jar with id: {jar_id} doesn't belong to product {}",
+                    statement.product_id
+                )
+            );
+
+            // Jars created or changed after `effective_at` are included into a statement for all jars
+            // since their checkpoint, so a new jar doesn't invalidate the statement
+            let jar_effective_at = if is_all_jars {
+                cmp::max(effective_at, jar.get_penalty_checkpoint())
+            } else {
+                effective_at
+            };
+
+            self.set_penalty_internal(&account_id, *jar_id, statement.value, jar_effective_at);
+        }
+
+        emit(BatchApplyPenalty(BatchPenaltyData {
+            jars: jar_ids,
+            is_applied: statement.value,
+            timestamp: effective_at,
+        }));
+    }
+
+    fn get_penalty_nonce(&self, product_id: ProductId, account_id: AccountId) -> U64 {
+        U64(self
+            .penalty_nonces
+            .get(&(product_id, account_id))
+            .copied()
+            .unwrap_or_default())
+    }
+}
+
+impl Contract {
+    fn get_penalty_effective_at(&self, effective_at: Option<U64>) -> Timestamp {
+        let now = env::block_timestamp_ms();
+        let effective_at = effective_at.map_or(now, |value| value.0);
+
+        require!(effective_at <= now, "Penalty can't be effective in the future");
+        require!(
+            now - effective_at <= self.max_penalty_lookback,
+            format!(
+                "Penalty can't be backdated by more than {} ms",
+                self.max_penalty_lookback
+            )
+        );
+
+        effective_at
+    }
+
+    fn set_penalty_internal(&mut self, account_id: &AccountId, jar_id: JarId, value: bool, effective_at: Timestamp) {
+        let jar = self.get_jar_internal(account_id, jar_id);
+        let product = self.get_product(&jar.product_id).clone();
+
+        assert_penalty_apy(&product.apy);
+
+        if jar.is_penalty_applied == value {
+            return;
+        }
+
+        let checkpoint = jar.get_penalty_checkpoint();
+
+        require!(
+            effective_at >= checkpoint,
+            format!("Penalty for jar with id: {jar_id} can't be effective before {checkpoint}")
+        );
+
+        let initial_storage_usage = self.flush_storage_usage();
+        self.get_jar_mut_internal(account_id, jar_id).apply_penalty(
+            &product,
+            value,
+            effective_at,
+            env::block_timestamp_ms(),
+        );
+        self.charge_storage_unchecked(account_id, initial_storage_usage);
+
+        self.record_activity(jar_id, penalty_activity_kind(value), 0);
+    }
+}
+
+fn penalty_activity_kind(is_applied: bool) -> JarActivityKind {
+    if is_applied {
+        JarActivityKind::ApplyPenalty
+    } else {
+        JarActivityKind::CancelPenalty
+    }
 }
 
 fn assert_penalty_apy(apy: &Apy) {
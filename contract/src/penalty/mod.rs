@@ -1 +1,4 @@
 pub mod api;
+pub mod model;
+mod tests;
+pub mod view;
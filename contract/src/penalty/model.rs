@@ -0,0 +1,69 @@
+use model::{
+    jar::{JarId, JarIdView},
+    ProductId,
+};
+use near_sdk::{
+    borsh::{self, BorshSerialize},
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+/// The `PenaltyStatement` struct represents a penalty change signed by the penalty oracle of a product.
+/// Anyone can submit it with `apply_signed_penalty`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PenaltyStatement {
+    /// The account which owns the jars.
+    pub account_id: AccountId,
+
+    /// The product of the jars. The statement is verified with the penalty key of this product.
+    pub product_id: ProductId,
+
+    /// IDs of the jars to apply the penalty to. `None` means all jars of the account in the product.
+    pub jar_ids: Option<Vec<JarIdView>>,
+
+    /// Whether the penalty should be applied (`true`) or canceled (`false`).
+    pub value: bool,
+
+    /// The timestamp in milliseconds the change is effective since. It's required, so a statement
+    /// can't become effective later than the oracle observed the violation.
+    pub effective_at: U64,
+
+    /// A number which must be greater than the nonce of the last statement applied to the account within the product.
+    pub nonce: U64,
+}
+
+/// The data signed by the penalty oracle. It's serialized with Borsh, so every field is unambiguously
+/// delimited from the next one.
+#[derive(BorshSerialize)]
+struct PenaltySignatureMaterial {
+    domain: String,
+    contract_account_id: AccountId,
+    account_id: AccountId,
+    product_id: ProductId,
+    jar_ids: Option<Vec<JarId>>,
+    value: bool,
+    effective_at: u64,
+    nonce: u64,
+}
+
+impl PenaltyStatement {
+    pub(crate) fn get_signature_material(&self, contract_account_id: &AccountId) -> Vec<u8> {
+        PenaltySignatureMaterial {
+            domain: "penalty".to_string(),
+            contract_account_id: contract_account_id.clone(),
+            account_id: self.account_id.clone(),
+            product_id: self.product_id.clone(),
+            jar_ids: self
+                .jar_ids
+                .as_ref()
+                .map(|jar_ids| jar_ids.iter().map(|jar_id| jar_id.0).collect()),
+            value: self.value,
+            effective_at: self.effective_at.0,
+            nonce: self.nonce.0,
+        }
+        .try_to_vec()
+        .unwrap_or_else(|_| near_sdk::env::panic_str("Failed to serialize penalty statement"))
+    }
+}
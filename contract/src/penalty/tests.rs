@@ -0,0 +1,387 @@
+#![cfg(test)]
+
+use model::U32;
+use near_sdk::{
+    json_types::{Base64VecU8, U64},
+    test_utils::accounts,
+    AccountId,
+};
+
+use crate::{
+    claim::api::ClaimApi,
+    common::{tests::Context, udecimal::UDecimal, MS_IN_YEAR},
+    history::api::HistoryApi,
+    jar::{
+        api::JarApi,
+        model::{Jar, MAX_PENALTY_INTERVALS},
+    },
+    penalty::{api::PenaltyApi, model::PenaltyStatement, view::PenaltyIntervalView},
+    product::{
+        helpers::MessageSigner,
+        model::{Apy, DowngradableApy, Product},
+    },
+};
+
+const HALF_YEAR: u64 = MS_IN_YEAR / 2;
+const QUARTER: u64 = MS_IN_YEAR / 4;
+
+fn generate_premium_product(id: &str) -> Product {
+    Product::generate(id)
+        .enabled(true)
+        .lockup_term(MS_IN_YEAR)
+        .apy(Apy::Downgradable(DowngradableApy {
+            default: UDecimal::new(20, 2),
+            fallback: UDecimal::new(10, 2),
+        }))
+}
+
+fn prepare_context() -> (AccountId, AccountId, Context) {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = generate_premium_product("premium_product");
+    let jar = Jar::generate(0, &alice, &product.id).principal(100_000_000);
+    let mut context = Context::new(admin.clone()).with_products(&[product]).with_jars(&[jar]);
+
+    context.switch_account(&admin);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_max_penalty_lookback(U64(HALF_YEAR));
+    });
+
+    (alice, admin, context)
+}
+
+#[test]
+fn backdated_penalty_applies_since_effective_date() {
+    let (alice, _, mut context) = prepare_context();
+
+    context.set_block_timestamp_in_ms(HALF_YEAR);
+    context
+        .contract
+        .set_penalty(alice.clone(), U32(0), true, Some(U64(QUARTER)));
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR);
+
+    let interest = context.contract.get_total_interest(alice).amount.total.0;
+    assert_eq!(12_500_000, interest);
+}
+
+#[test]
+fn penalty_history_contains_intervals() {
+    let (alice, _, mut context) = prepare_context();
+
+    context.set_block_timestamp_in_ms(HALF_YEAR);
+    context
+        .contract
+        .set_penalty(alice.clone(), U32(0), true, Some(U64(QUARTER)));
+    context
+        .contract
+        .set_penalty(alice.clone(), U32(0), false, Some(U64(HALF_YEAR - 1)));
+    context.contract.set_penalty(alice.clone(), U32(0), true, None);
+
+    assert_eq!(
+        vec![
+            PenaltyIntervalView {
+                started_at: U64(QUARTER),
+                ended_at: Some(U64(HALF_YEAR - 1)),
+            },
+            PenaltyIntervalView {
+                started_at: U64(HALF_YEAR),
+                ended_at: None,
+            },
+        ],
+        context.contract.get_penalty_history(alice.clone(), U32(0))
+    );
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR);
+
+    let interest = context.contract.get_total_interest(alice).amount.total.0;
+    assert_eq!(12_500_000, interest);
+}
+
+#[test]
+fn backdated_penalty_takes_back_interest_accrued_after_claim() {
+    let (alice, admin, mut context) = prepare_context();
+
+    context.set_block_timestamp_in_ms(QUARTER);
+    context.switch_account(&alice);
+    context.contract.claim_total();
+
+    context.set_block_timestamp_in_ms(HALF_YEAR);
+    context.switch_account(&admin);
+    context
+        .contract
+        .set_penalty(alice.clone(), U32(0), true, Some(U64(QUARTER / 2)));
+
+    assert_eq!(
+        1_250_000,
+        context.contract.get_total_interest(alice.clone()).amount.total.0
+    );
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR);
+
+    let interest = context.contract.get_total_interest(alice).amount.total.0;
+    assert_eq!(6_250_000, interest);
+}
+
+#[test]
+fn setting_same_penalty_status_records_no_activity() {
+    let (alice, admin, mut context) = prepare_context();
+
+    context.switch_account(&admin);
+    context.with_deposit_yocto(1, |context| context.contract.set_history_capacity(10));
+
+    context.set_block_timestamp_in_ms(QUARTER);
+    context.contract.set_penalty(alice.clone(), U32(0), false, None);
+
+    assert!(context
+        .contract
+        .get_jar_history(U32(0), None, None)
+        .activities
+        .is_empty());
+}
+
+#[test]
+fn penalty_intervals_are_capped() {
+    let (alice, _, mut context) = prepare_context();
+
+    for i in 1..=MAX_PENALTY_INTERVALS as u64 + 2 {
+        context.set_block_timestamp_in_ms(i * 2);
+        context.contract.set_penalty(alice.clone(), U32(0), true, None);
+
+        context.set_block_timestamp_in_ms(i * 2 + 1);
+        context.contract.set_penalty(alice.clone(), U32(0), false, None);
+    }
+
+    let history = context.contract.get_penalty_history(alice, U32(0));
+    assert_eq!(MAX_PENALTY_INTERVALS, history.len());
+    assert_eq!(U64(6), history[0].started_at);
+}
+
+#[test]
+#[should_panic(expected = "Penalty can't be backdated by more than 15768000000 ms")]
+fn penalty_backdated_beyond_lookback() {
+    let (alice, _, mut context) = prepare_context();
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR);
+    context.contract.set_penalty(alice, U32(0), true, Some(U64(QUARTER)));
+}
+
+#[test]
+#[should_panic(expected = "Penalty can't be effective in the future")]
+fn penalty_effective_in_future() {
+    let (alice, _, mut context) = prepare_context();
+
+    context.set_block_timestamp_in_ms(QUARTER);
+    context.contract.set_penalty(alice, U32(0), true, Some(U64(HALF_YEAR)));
+}
+
+#[test]
+#[should_panic(expected = "Penalty for jar with id: 0 can't be effective before 7884000000")]
+fn penalty_canceled_before_it_was_applied() {
+    let (alice, _, mut context) = prepare_context();
+
+    context.set_block_timestamp_in_ms(HALF_YEAR);
+    context
+        .contract
+        .set_penalty(alice.clone(), U32(0), true, Some(U64(QUARTER)));
+    context
+        .contract
+        .set_penalty(alice, U32(0), false, Some(U64(QUARTER - 1)));
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn set_max_penalty_lookback_by_not_manager() {
+    let (alice, _, mut context) = prepare_context();
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_max_penalty_lookback(U64(MS_IN_YEAR));
+    });
+}
+
+fn prepare_signed_context(product_signer: &MessageSigner) -> (AccountId, AccountId, Context) {
+    let alice = accounts(0);
+    let relayer = accounts(1);
+    let admin = accounts(2);
+
+    let product = generate_premium_product("premium_product").public_key(product_signer.public_key());
+    let other_product = generate_premium_product("other_product");
+    let jars = [
+        Jar::generate(0, &alice, &product.id).principal(100_000_000),
+        Jar::generate(1, &alice, &product.id).principal(100_000_000),
+        Jar::generate(2, &alice, &other_product.id).principal(100_000_000),
+    ];
+    let mut context = Context::new(admin)
+        .with_products(&[product, other_product])
+        .with_jars(&jars);
+
+    context.switch_account(&relayer);
+
+    (alice, relayer, context)
+}
+
+fn statement(account_id: &AccountId, jar_ids: Option<Vec<u32>>, nonce: u64) -> PenaltyStatement {
+    PenaltyStatement {
+        account_id: account_id.clone(),
+        product_id: "premium_product".to_string(),
+        jar_ids: jar_ids.map(|jar_ids| jar_ids.into_iter().map(U32).collect()),
+        value: true,
+        effective_at: U64(0),
+        nonce: U64(nonce),
+    }
+}
+
+fn sign(context: &Context, signer: &MessageSigner, statement: &PenaltyStatement) -> Base64VecU8 {
+    signer
+        .sign_bytes(&statement.get_signature_material(&context.owner))
+        .into()
+}
+
+#[test]
+fn apply_signed_penalty_to_all_jars_of_product() {
+    let signer = MessageSigner::new();
+    let (alice, _, mut context) = prepare_signed_context(&signer);
+
+    let statement = statement(&alice, None, 1);
+    let signature = sign(&context, &signer, &statement);
+
+    context.contract.apply_signed_penalty(statement, signature);
+
+    assert!(context.contract.get_jar_internal(&alice, 0).is_penalty_applied);
+    assert!(context.contract.get_jar_internal(&alice, 1).is_penalty_applied);
+    assert!(!context.contract.get_jar_internal(&alice, 2).is_penalty_applied);
+    assert_eq!(
+        U64(1),
+        context
+            .contract
+            .get_penalty_nonce("premium_product".to_string(), alice.clone())
+    );
+    assert_eq!(
+        U64(0),
+        context.contract.get_penalty_nonce("other_product".to_string(), alice)
+    );
+}
+
+#[test]
+fn apply_signed_penalty_to_all_jars_including_newer_ones() {
+    let signer = MessageSigner::new();
+    let (alice, relayer, mut context) = prepare_signed_context(&signer);
+
+    context.switch_account(&context.contract.manager.clone());
+    context.with_deposit_yocto(1, |context| {
+        context.contract.set_max_penalty_lookback(U64(MS_IN_YEAR));
+    });
+    context.switch_account(&relayer);
+
+    context.set_block_timestamp_in_ms(HALF_YEAR);
+    let new_jar = Jar::generate(3, &alice, &"premium_product".to_string())
+        .principal(100_000_000)
+        .created_at(QUARTER);
+    let mut context = context.with_jars(&[new_jar]);
+
+    let statement = statement(&alice, None, 1);
+    let signature = sign(&context, &signer, &statement);
+
+    context.contract.apply_signed_penalty(statement, signature);
+
+    assert!(context.contract.get_jar_internal(&alice, 0).is_penalty_applied);
+    assert_eq!(
+        vec![PenaltyIntervalView {
+            started_at: U64(QUARTER),
+            ended_at: None,
+        }],
+        context.contract.get_penalty_history(alice, U32(3))
+    );
+}
+
+#[test]
+fn apply_signed_penalty_with_penalty_key() {
+    let signer = MessageSigner::new();
+    let penalty_signer = MessageSigner::new();
+    let (alice, relayer, mut context) = prepare_signed_context(&signer);
+
+    context.switch_account(&context.contract.manager.clone());
+    context.with_deposit_yocto(1, |context| {
+        context
+            .contract
+            .set_penalty_key("premium_product".to_string(), Some(penalty_signer.public_key().into()));
+    });
+    context.switch_account(&relayer);
+
+    let statement = statement(&alice, Some(vec![1]), 1);
+    let signature = sign(&context, &penalty_signer, &statement);
+
+    context.contract.apply_signed_penalty(statement, signature);
+
+    assert!(!context.contract.get_jar_internal(&alice, 0).is_penalty_applied);
+    assert!(context.contract.get_jar_internal(&alice, 1).is_penalty_applied);
+}
+
+#[test]
+#[should_panic(expected = "Not matching signature")]
+fn apply_penalty_signed_with_another_key() {
+    let signer = MessageSigner::new();
+    let (alice, _, mut context) = prepare_signed_context(&signer);
+
+    let statement = statement(&alice, None, 1);
+    let signature = sign(&context, &MessageSigner::new(), &statement);
+
+    context.contract.apply_signed_penalty(statement, signature);
+}
+
+#[test]
+#[should_panic(expected = "Penalty statement nonce must be greater than 1")]
+fn replay_signed_penalty() {
+    let signer = MessageSigner::new();
+    let (alice, _, mut context) = prepare_signed_context(&signer);
+
+    let statement = statement(&alice, None, 1);
+    let signature = sign(&context, &signer, &statement);
+
+    context
+        .contract
+        .apply_signed_penalty(statement.clone(), signature.clone());
+    context.contract.apply_signed_penalty(statement, signature);
+}
+
+#[test]
+#[should_panic(expected = "Jar with id: 2 doesn't belong to product premium_product")]
+fn apply_signed_penalty_to_jar_of_another_product() {
+    let signer = MessageSigner::new();
+    let (alice, _, mut context) = prepare_signed_context(&signer);
+
+    let statement = statement(&alice, Some(vec![0, 2]), 1);
+    let signature = sign(&context, &signer, &statement);
+
+    context.contract.apply_signed_penalty(statement, signature);
+}
+
+#[test]
+fn penalty_nonces_are_tracked_per_product() {
+    let signer = MessageSigner::new();
+    let other_signer = MessageSigner::new();
+    let (alice, relayer, mut context) = prepare_signed_context(&signer);
+
+    context.switch_account(&context.contract.manager.clone());
+    context.with_deposit_yocto(1, |context| {
+        context
+            .contract
+            .set_penalty_key("other_product".to_string(), Some(other_signer.public_key().into()));
+    });
+    context.switch_account(&relayer);
+
+    let premium_statement = statement(&alice, None, 5);
+    let signature = sign(&context, &signer, &premium_statement);
+    context.contract.apply_signed_penalty(premium_statement, signature);
+
+    let other_statement = PenaltyStatement {
+        product_id: "other_product".to_string(),
+        ..statement(&alice, None, 1)
+    };
+    let signature = sign(&context, &other_signer, &other_statement);
+    context.contract.apply_signed_penalty(other_statement, signature);
+
+    assert!(context.contract.get_jar_internal(&alice, 2).is_penalty_applied);
+}
@@ -0,0 +1,22 @@
+use near_sdk::{
+    json_types::U64,
+    serde::{Deserialize, Serialize},
+};
+
+use crate::jar::model::PenaltyInterval;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PenaltyIntervalView {
+    pub started_at: U64,
+    pub ended_at: Option<U64>,
+}
+
+impl From<&PenaltyInterval> for PenaltyIntervalView {
+    fn from(value: &PenaltyInterval) -> Self {
+        Self {
+            started_at: U64(value.started_at),
+            ended_at: value.ended_at.map(U64),
+        }
+    }
+}
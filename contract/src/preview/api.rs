@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use model::{jar::JarIdView, withdraw::WithdrawView, TokenAmount, U32};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId};
+
+use crate::{
+    jar::view::AggregatedTokenAmountView,
+    preview::view::{ClaimPreviewView, PreviewView, RestakePreviewView, TopUpPreviewView, WithdrawPreviewView},
+    Contract, ContractExt,
+};
+
+/// The `PreviewApi` trait defines view methods describing what state-changing calls would do
+/// if they were called at the moment. They share validation with the calls they describe,
+/// so a failure contains the exact reason the call would panic with.
+pub trait PreviewApi {
+    /// Describes the result of `withdraw` called by the owner of a jar.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jar owner.
+    /// * `jar_id` - The ID of the deposit jar to withdraw from.
+    /// * `amount` - An optional amount of tokens to withdraw. If not provided, the whole principal is withdrawn.
+    ///
+    /// # Returns
+    ///
+    /// A `PreviewView` containing either the amount to be transferred, the fee, the principal left in the jar
+    /// and whether the jar would be closed, or the reason why the withdrawal would fail.
+    fn preview_withdraw(
+        &self,
+        account_id: AccountId,
+        jar_id: JarIdView,
+        amount: Option<U128>,
+    ) -> PreviewView<WithdrawPreviewView>;
+
+    /// Describes the result of `claim_jars` called by the owner of the jars.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jars owner.
+    /// * `jar_ids` - A `Vec<JarIdView>` containing the IDs of the deposit jars to claim interest from.
+    /// * `amount` - An optional amount of tokens to claim.
+    ///
+    /// # Returns
+    ///
+    /// A `PreviewView` containing the interest to be claimed from every jar and the jars that would be closed.
+    fn preview_claim_jars(
+        &self,
+        account_id: AccountId,
+        jar_ids: Vec<JarIdView>,
+        amount: Option<U128>,
+    ) -> PreviewView<ClaimPreviewView>;
+
+    /// Describes the result of `restake` called by the owner of a jar.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jar owner.
+    /// * `jar_id` - The ID of the deposit jar to restake.
+    ///
+    /// # Returns
+    ///
+    /// A `PreviewView` containing either the principal of the new jar and whether the original jar
+    /// would be closed, or the reason why the restaking would fail.
+    fn preview_restake(&self, account_id: AccountId, jar_id: JarIdView) -> PreviewView<RestakePreviewView>;
+
+    /// Describes the result of topping up a jar with `ft_transfer_call`.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the jar owner.
+    /// * `jar_id` - The ID of the deposit jar to top up.
+    /// * `amount` - The amount of tokens to add to the principal.
+    ///
+    /// # Returns
+    ///
+    /// A `PreviewView` containing either the principal of the jar after the top-up,
+    /// or the reason why the top-up would fail.
+    fn preview_top_up(&self, account_id: AccountId, jar_id: JarIdView, amount: U128) -> PreviewView<TopUpPreviewView>;
+}
+
+#[near_bindgen]
+impl PreviewApi for Contract {
+    fn preview_withdraw(
+        &self,
+        account_id: AccountId,
+        jar_id: JarIdView,
+        amount: Option<U128>,
+    ) -> PreviewView<WithdrawPreviewView> {
+        let now = env::block_timestamp_ms();
+
+        self.check_withdraw(&account_id, jar_id.0, amount, now)
+            .map(|(jar, amount)| {
+                let product = self.get_product(&jar.product_id);
+                let withdrawn_jar = jar.withdrawn(product, amount, now);
+                let withdrawal = WithdrawView::new(amount, self.get_fee(product, jar));
+
+                WithdrawPreviewView {
+                    withdrawn_amount: withdrawal.withdrawn_amount,
+                    fee: withdrawal.fee,
+                    principal: U128(withdrawn_jar.principal),
+                    close_jar: withdrawn_jar.should_be_closed(product, now),
+                }
+            })
+            .into()
+    }
+
+    fn preview_claim_jars(
+        &self,
+        account_id: AccountId,
+        jar_ids: Vec<JarIdView>,
+        amount: Option<U128>,
+    ) -> PreviewView<ClaimPreviewView> {
+        let now = env::block_timestamp_ms();
+
+        if let Err(reason) = self.check_not_blocked(&account_id) {
+            return PreviewView::Failure(reason);
+        }
+
+        let mut detailed_amounts = HashMap::<JarIdView, U128>::new();
+        let mut total_amount: TokenAmount = 0;
+        let mut closed_jars = vec![];
+
+        for (mut jar, available_interest, interest_to_claim) in
+            self.get_claimable_jars(&account_id, &jar_ids, amount, now)
+        {
+            if interest_to_claim == 0 {
+                continue;
+            }
+
+            jar.claim(available_interest, interest_to_claim, now);
+
+            detailed_amounts.insert(U32(jar.id), U128(interest_to_claim));
+            total_amount += interest_to_claim;
+
+            if jar.should_be_closed(self.get_product(&jar.product_id), now) {
+                closed_jars.push(U32(jar.id));
+            }
+        }
+
+        PreviewView::Success(ClaimPreviewView {
+            amount: AggregatedTokenAmountView {
+                detailed: detailed_amounts,
+                total: U128(total_amount),
+            },
+            closed_jars,
+        })
+    }
+
+    fn preview_restake(&self, account_id: AccountId, jar_id: JarIdView) -> PreviewView<RestakePreviewView> {
+        let now = env::block_timestamp_ms();
+
+        self.find_restakable_jar(&account_id, jar_id.0, now)
+            .and_then(|jar| {
+                let product = self.get_product(&jar.product_id);
+                product.check_cap(jar.principal)?;
+
+                let withdrawn_jar = jar.withdrawn(product, jar.principal, now);
+
+                Ok(RestakePreviewView {
+                    principal: U128(jar.principal),
+                    close_jar: withdrawn_jar.should_be_closed(product, now),
+                })
+            })
+            .into()
+    }
+
+    fn preview_top_up(&self, account_id: AccountId, jar_id: JarIdView, amount: U128) -> PreviewView<TopUpPreviewView> {
+        self.check_top_up(&account_id, jar_id.0, amount.0)
+            .map(|(jar, _)| TopUpPreviewView {
+                principal: U128(jar.principal + amount.0),
+            })
+            .into()
+    }
+}
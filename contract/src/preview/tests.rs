@@ -0,0 +1,187 @@
+#![cfg(test)]
+
+use model::U32;
+use near_sdk::{json_types::U128, test_utils::accounts, AccountId, PromiseOrValue};
+
+use crate::{
+    common::{tests::Context, udecimal::UDecimal, MS_IN_YEAR},
+    jar::model::{Jar, JarCache},
+    preview::{
+        api::PreviewApi,
+        view::{PreviewView, RestakePreviewView, TopUpPreviewView, WithdrawPreviewView},
+    },
+    product::model::{Apy, Product, WithdrawalFee},
+    withdraw::api::WithdrawApi,
+};
+
+fn prepare_jar(product: &Product) -> (AccountId, Context) {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let context = Context::new(admin).with_products(&[product.clone()]).with_jars(&[jar]);
+
+    (alice, context)
+}
+
+fn generate_product() -> Product {
+    Product::generate("product")
+        .enabled(true)
+        .lockup_term(MS_IN_YEAR)
+        .apy(Apy::Constant(UDecimal::new(12, 2)))
+        .cap(0, 10_000_000)
+}
+
+#[test]
+fn preview_withdraw_matches_withdrawal() {
+    let product = generate_product().with_withdrawal_fee(WithdrawalFee::Fix(100));
+    let (alice, mut context) = prepare_jar(&product);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    let preview = context
+        .contract
+        .preview_withdraw(alice.clone(), U32(0), Some(U128(400_000)));
+    assert_eq!(
+        PreviewView::Success(WithdrawPreviewView {
+            withdrawn_amount: U128(399_900),
+            fee: U128(100),
+            principal: U128(600_000),
+            close_jar: false,
+        }),
+        preview
+    );
+
+    context.switch_account(&alice);
+    let PromiseOrValue::Value(withdrawal) = context.contract.withdraw(U32(0), Some(U128(400_000)), None, None, None)
+    else {
+        panic!("Invalid promise type");
+    };
+    assert_eq!(399_900, withdrawal.withdrawn_amount.0);
+    assert_eq!(100, withdrawal.fee.0);
+}
+
+#[test]
+fn preview_withdraw_before_maturity() {
+    let product = generate_product();
+    let (alice, mut context) = prepare_jar(&product);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR / 2);
+
+    let preview = context.contract.preview_withdraw(alice, U32(0), None);
+    assert_eq!(PreviewView::Failure("The jar is not mature yet".to_string()), preview);
+}
+
+#[test]
+fn preview_withdraw_from_not_existing_jar() {
+    let product = generate_product();
+    let (alice, context) = prepare_jar(&product);
+
+    let preview = context.contract.preview_withdraw(alice, U32(1), None);
+    assert_eq!(
+        PreviewView::Failure("Jar with id: 1 doesn't exist".to_string()),
+        preview
+    );
+}
+
+#[test]
+fn preview_claim_with_closing_jar() {
+    let product = generate_product();
+    let (alice, mut context) = prepare_jar(&product);
+
+    let mut empty_jar = Jar::generate(1, &alice, &product.id).principal(0);
+    empty_jar.cache = Some(JarCache {
+        updated_at: 0,
+        interest: 500,
+    });
+    context = context.with_jars(&[empty_jar]);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR / 2);
+
+    let PreviewView::Success(preview) = context.contract.preview_claim_jars(alice, vec![U32(0), U32(1)], None) else {
+        panic!("Claim preview must succeed");
+    };
+    assert_eq!(60_000, preview.amount.detailed[&U32(0)].0);
+    assert_eq!(500, preview.amount.detailed[&U32(1)].0);
+    assert_eq!(60_500, preview.amount.total.0);
+    assert_eq!(vec![U32(1)], preview.closed_jars);
+}
+
+#[test]
+fn preview_restake_of_mature_jar() {
+    let product = generate_product().with_allows_restaking(true);
+    let (alice, mut context) = prepare_jar(&product);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    let preview = context.contract.preview_restake(alice, U32(0));
+    assert_eq!(
+        PreviewView::Success(RestakePreviewView {
+            principal: U128(1_000_000),
+            close_jar: false,
+        }),
+        preview
+    );
+}
+
+#[test]
+fn preview_restake_when_restaking_is_not_supported() {
+    let product = generate_product().with_allows_restaking(false);
+    let (alice, mut context) = prepare_jar(&product);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    let preview = context.contract.preview_restake(alice, U32(0));
+    assert_eq!(
+        PreviewView::Failure("The product doesn't support restaking".to_string()),
+        preview
+    );
+}
+
+#[test]
+fn preview_top_up_within_and_out_of_cap() {
+    let product = generate_product().with_allows_top_up(true);
+    let (alice, context) = prepare_jar(&product);
+
+    let preview = context.contract.preview_top_up(alice.clone(), U32(0), U128(500_000));
+    assert_eq!(
+        PreviewView::Success(TopUpPreviewView {
+            principal: U128(1_500_000)
+        }),
+        preview
+    );
+
+    let preview = context.contract.preview_top_up(alice, U32(0), U128(10_000_000));
+    assert_eq!(
+        PreviewView::Failure("Total amount is out of product bounds: [0..10000000]".to_string()),
+        preview
+    );
+}
+
+#[test]
+fn previews_for_blocked_account() {
+    let product = generate_product().with_allows_top_up(true);
+    let (alice, mut context) = prepare_jar(&product);
+
+    context.contract.blocked_accounts.insert(alice.clone());
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+
+    let reason = format!("Account '{alice}' is blocked");
+
+    assert_eq!(
+        PreviewView::Failure(reason.clone()),
+        context.contract.preview_withdraw(alice.clone(), U32(0), None)
+    );
+    assert_eq!(
+        PreviewView::Failure(reason.clone()),
+        context.contract.preview_claim_jars(alice.clone(), vec![U32(0)], None)
+    );
+    assert_eq!(
+        PreviewView::Failure(reason.clone()),
+        context.contract.preview_restake(alice.clone(), U32(0))
+    );
+    assert_eq!(
+        PreviewView::Failure(reason),
+        context.contract.preview_top_up(alice, U32(0), U128(500_000))
+    );
+}
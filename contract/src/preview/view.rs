@@ -0,0 +1,73 @@
+use model::jar::JarIdView;
+use near_sdk::{
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+};
+
+use crate::jar::view::AggregatedTokenAmountView;
+
+/// The `PreviewView` enum represents the expected outcome of a state-changing call:
+/// either its result or the reason why the call would fail.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(
+    crate = "near_sdk::serde",
+    tag = "status",
+    content = "data",
+    rename_all = "snake_case"
+)]
+pub enum PreviewView<T> {
+    Success(T),
+    Failure(String),
+}
+
+impl<T> From<Result<T, String>> for PreviewView<T> {
+    fn from(value: Result<T, String>) -> Self {
+        match value {
+            Ok(value) => PreviewView::Success(value),
+            Err(reason) => PreviewView::Failure(reason),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WithdrawPreviewView {
+    /// The amount of tokens that would be transferred to the owner.
+    pub withdrawn_amount: U128,
+
+    /// The fee that would be charged for the withdrawal.
+    pub fee: U128,
+
+    /// The principal left in the jar after the withdrawal.
+    pub principal: U128,
+
+    /// Indicates whether the jar would be closed after the withdrawal.
+    pub close_jar: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClaimPreviewView {
+    /// The interest that would be claimed from every jar and in total.
+    pub amount: AggregatedTokenAmountView,
+
+    /// The IDs of jars that would be closed after the claim.
+    pub closed_jars: Vec<JarIdView>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RestakePreviewView {
+    /// The principal of the new jar.
+    pub principal: U128,
+
+    /// Indicates whether the original jar would be closed after the restaking.
+    pub close_jar: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TopUpPreviewView {
+    /// The principal of the jar after the top-up.
+    pub principal: U128,
+}
@@ -1,8 +1,13 @@
 use model::ProductId;
-use near_sdk::{assert_one_yocto, near_bindgen, require};
+use near_sdk::{
+    assert_one_yocto, env,
+    json_types::{U128, U64},
+    near_bindgen, require,
+};
 
 use crate::{
     event::{emit, ChangeProductPublicKeyData, EnableProductData, EventKind},
+    jar::model::Jar,
     product::{command::RegisterProductCommand, model::Product, view::ProductView},
     Base64VecU8, Contract, ContractExt,
 };
@@ -53,6 +58,28 @@ pub trait ProductApi {
     ///
     /// A `Vec<ProductView>` containing information about all registered products.
     fn get_products(&self) -> Vec<ProductView>;
+
+    /// Calculates the interest that a new jar of the specified product would accrue.
+    ///
+    /// The interest is calculated the same way as for existing jars, so it matches the amount
+    /// the contract will pay for a jar created right now. For Fixed products the interest stops
+    /// accruing at maturity, so a duration longer than the lockup term doesn't increase it.
+    ///
+    /// # Arguments
+    ///
+    /// * `product_id` - The ID of the product for which the quote is being calculated.
+    /// * `amount` - The principal of the hypothetical jar.
+    /// * `duration` - The period in milliseconds for which the interest is calculated.
+    ///
+    /// # Returns
+    ///
+    /// An `U128` representing the interest accrued by the jar after the specified period.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the product doesn't exist, the amount is out of the product bounds
+    /// or the end of the period overflows the timestamp.
+    fn quote_product(&self, product_id: ProductId, amount: U128, duration: U64) -> U128;
 }
 
 #[near_bindgen]
@@ -106,4 +133,18 @@ impl ProductApi for Contract {
     fn get_products(&self) -> Vec<ProductView> {
         self.products.values().map(|product| product.clone().into()).collect()
     }
+
+    fn quote_product(&self, product_id: ProductId, amount: U128, duration: U64) -> U128 {
+        let product = self.get_product(&product_id);
+        product.assert_cap(amount.0);
+
+        let now = env::block_timestamp_ms();
+        let Some(until) = now.checked_add(duration.0) else {
+            env::panic_str("Quote duration is too long");
+        };
+
+        let jar = Jar::create(0, env::predecessor_account_id(), product_id, amount.0, now);
+
+        U128(jar.get_interest(product, until))
+    }
 }
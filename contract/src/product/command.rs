@@ -7,7 +7,9 @@ use near_sdk::{
 
 use crate::{
     common::udecimal::UDecimal,
-    product::model::{Apy, Cap, DowngradableApy, FixedProductTerms, Product, Terms, WithdrawalFee},
+    product::model::{
+        Apy, Cap, DowngradableApy, FixedProductTerms, FlexibleProductTerms, Product, Terms, WithdrawalFee,
+    },
 };
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Clone, Debug)]
@@ -22,6 +24,10 @@ pub struct RegisterProductCommand {
     pub withdrawal_fee: Option<WithdrawalFeeDto>,
     pub public_key: Option<Base64VecU8>,
     pub is_enabled: bool,
+
+    /// Whether jars of the product can be transferred to another account. Defaults to `true`,
+    /// the same as for products migrated from the previous version. Set it to `false` to opt out of transfers.
+    pub is_transferable: Option<bool>,
 }
 
 #[cfg(test)]
@@ -37,6 +43,7 @@ impl Default for RegisterProductCommand {
             withdrawal_fee: None,
             public_key: None,
             is_enabled: true,
+            is_transferable: None,
         }
     }
 }
@@ -69,6 +76,7 @@ impl From<RegisterProductCommand> for Product {
             withdrawal_fee,
             public_key: value.public_key.map(|key| key.0),
             is_enabled: value.is_enabled,
+            is_transferable: value.is_transferable.unwrap_or(true),
         }
     }
 }
@@ -77,7 +85,7 @@ impl From<RegisterProductCommand> for Product {
 #[serde(crate = "near_sdk::serde", tag = "type", content = "data", rename_all = "snake_case")]
 pub enum TermsDto {
     Fixed(FixedProductTermsDto),
-    Flexible,
+    Flexible(Option<FlexibleProductTermsDto>),
 }
 
 #[cfg(test)]
@@ -95,6 +103,12 @@ pub struct FixedProductTermsDto {
     pub allows_restaking: bool,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, PartialEq, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FlexibleProductTermsDto {
+    pub unbonding_period: Option<U64>,
+}
+
 #[cfg(test)]
 impl Default for FixedProductTermsDto {
     fn default() -> Self {
@@ -114,7 +128,9 @@ impl From<TermsDto> for Terms {
                 allows_top_up: value.allows_top_up,
                 allows_restaking: value.allows_restaking,
             }),
-            TermsDto::Flexible => Terms::Flexible,
+            TermsDto::Flexible(value) => Terms::Flexible(FlexibleProductTerms {
+                unbonding_period: value.and_then(|value| value.unbonding_period).map(|period| period.0),
+            }),
         }
     }
 }
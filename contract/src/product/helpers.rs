@@ -12,7 +12,7 @@ use rand::rngs::OsRng;
 use crate::{
     common::{tests::Context, udecimal::UDecimal, Duration, MS_IN_YEAR},
     jar::model::JarTicket,
-    product::model::{Apy, Cap, FixedProductTerms, Product, Terms, WithdrawalFee},
+    product::model::{Apy, Cap, FixedProductTerms, FlexibleProductTerms, Product, Terms, WithdrawalFee},
     Contract,
 };
 
@@ -29,7 +29,11 @@ impl MessageSigner {
     }
 
     pub(crate) fn sign(&self, message: &str) -> Vec<u8> {
-        let message_hash = digest(Algorithm::SHA256, message.as_bytes());
+        self.sign_bytes(message.as_bytes())
+    }
+
+    pub(crate) fn sign_bytes(&self, message: &[u8]) -> Vec<u8> {
+        let message_hash = digest(Algorithm::SHA256, message);
         let signature = self.signing_key.sign(message_hash.as_slice());
         signature.to_bytes().to_vec()
     }
@@ -60,6 +64,7 @@ impl Product {
             withdrawal_fee: None,
             public_key: None,
             is_enabled: true,
+            is_transferable: true,
         }
     }
 
@@ -73,13 +78,25 @@ impl Product {
         self
     }
 
+    pub(crate) fn transferable(mut self, transferable: bool) -> Self {
+        self.is_transferable = transferable;
+        self
+    }
+
     pub(crate) fn cap(mut self, min: TokenAmount, max: TokenAmount) -> Self {
         self.cap = Cap { min, max };
         self
     }
 
     pub(crate) fn flexible(mut self) -> Self {
-        self.terms = Terms::Flexible;
+        self.terms = Terms::Flexible(FlexibleProductTerms::default());
+        self
+    }
+
+    pub(crate) fn unbonding_period(mut self, period: Duration) -> Self {
+        self.terms = Terms::Flexible(FlexibleProductTerms {
+            unbonding_period: Some(period),
+        });
         self
     }
 
@@ -94,7 +111,7 @@ impl Product {
                 lockup_term: term,
                 ..terms
             }),
-            Terms::Flexible => Terms::Fixed(FixedProductTerms {
+            Terms::Flexible(_) => Terms::Fixed(FixedProductTerms {
                 lockup_term: term,
                 allows_top_up: false,
                 allows_restaking: false,
@@ -107,7 +124,7 @@ impl Product {
     pub(crate) fn with_allows_top_up(mut self, allows_top_up: bool) -> Self {
         self.terms = match self.terms {
             Terms::Fixed(terms) => Terms::Fixed(FixedProductTerms { allows_top_up, ..terms }),
-            Terms::Flexible => Terms::Fixed(FixedProductTerms {
+            Terms::Flexible(_) => Terms::Fixed(FixedProductTerms {
                 allows_top_up,
                 lockup_term: MS_IN_YEAR,
                 allows_restaking: false,
@@ -123,7 +140,7 @@ impl Product {
                 allows_restaking,
                 ..terms
             }),
-            Terms::Flexible => Terms::Fixed(FixedProductTerms {
+            Terms::Flexible(_) => Terms::Fixed(FixedProductTerms {
                 allows_restaking,
                 lockup_term: MS_IN_YEAR,
                 allows_top_up: false,
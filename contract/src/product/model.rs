@@ -34,6 +34,9 @@ pub struct Product {
 
     /// Indicates whether it's possible to create a new jar for this product.
     pub is_enabled: bool,
+
+    /// Indicates whether jars of this product can be transferred to another account.
+    pub is_transferable: bool,
 }
 
 /// The `Terms` enum describes additional terms specific to either Flexible or Fixed products.
@@ -44,7 +47,16 @@ pub enum Terms {
     Fixed(FixedProductTerms),
 
     /// Describes additional terms for Flexible products.
-    Flexible,
+    Flexible(FlexibleProductTerms),
+}
+
+/// The `FlexibleProductTerms` struct contains terms specific to Flexible products.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FlexibleProductTerms {
+    /// An optional period between a withdrawal request and the moment when the requested tokens
+    /// can be withdrawn. If it's set, withdrawal is a two-step process: `request_withdraw` and `finalize_withdraw`.
+    pub unbonding_period: Option<Duration>,
 }
 
 /// The `FixedProductTerms` struct contains terms specific to Fixed products.
@@ -108,31 +120,53 @@ pub struct Cap {
 
 impl Product {
     pub(crate) fn is_flexible(&self) -> bool {
-        self.terms == Terms::Flexible
+        matches!(self.terms, Terms::Flexible(_))
     }
 
     pub(crate) fn allows_top_up(&self) -> bool {
         self.is_enabled
             && match &self.terms {
                 Terms::Fixed(value) => value.allows_top_up,
-                Terms::Flexible => true,
+                Terms::Flexible(_) => true,
             }
     }
 
     pub(crate) fn allows_restaking(&self) -> bool {
         match &self.terms {
             Terms::Fixed(value) => value.allows_restaking,
-            Terms::Flexible => false,
+            Terms::Flexible(_) => false,
         }
     }
 
-    pub(crate) fn assert_cap(&self, amount: TokenAmount) {
+    pub(crate) fn get_lockup_term(&self) -> Option<Duration> {
+        match &self.terms {
+            Terms::Fixed(value) => Some(value.lockup_term),
+            Terms::Flexible(_) => None,
+        }
+    }
+
+    pub(crate) fn get_unbonding_period(&self) -> Option<Duration> {
+        match &self.terms {
+            Terms::Fixed(_) => None,
+            Terms::Flexible(value) => value.unbonding_period,
+        }
+    }
+
+    pub(crate) fn check_cap(&self, amount: TokenAmount) -> Result<(), String> {
         if self.cap.min > amount || amount > self.cap.max {
-            env::panic_str(&format!(
+            return Err(format!(
                 "Total amount is out of product bounds: [{}..{}]",
                 self.cap.min, self.cap.max
             ));
         }
+
+        Ok(())
+    }
+
+    pub(crate) fn assert_cap(&self, amount: TokenAmount) {
+        if let Err(message) = self.check_cap(amount) {
+            env::panic_str(&message);
+        }
     }
 
     pub(crate) fn assert_enabled(&self) {
@@ -156,13 +190,3 @@ impl Product {
         );
     }
 }
-
-#[cfg(test)]
-impl Product {
-    pub(crate) fn get_lockup_term(&self) -> Option<Duration> {
-        match self.clone().terms {
-            Terms::Fixed(value) => Some(value.lockup_term),
-            Terms::Flexible => None,
-        }
-    }
-}
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
 use near_sdk::{
-    json_types::{Base64VecU8, U128},
+    json_types::{Base64VecU8, U128, U64},
+    serde_json::{json, to_value},
     test_utils::accounts,
 };
 
@@ -11,7 +12,8 @@ use crate::{
         api::ProductApi,
         command::{RegisterProductCommand, TermsDto, WithdrawalFeeDto},
         helpers::MessageSigner,
-        model::{Apy, DowngradableApy, Product, Terms, WithdrawalFee},
+        model::{Apy, DowngradableApy, FlexibleProductTerms, Product, Terms, WithdrawalFee},
+        view::ProductView,
     },
 };
 
@@ -109,6 +111,7 @@ fn register_downgradable_product() {
             },
         })
     );
+    assert!(product.is_transferable);
 }
 
 #[test]
@@ -164,11 +167,14 @@ fn register_product_with_fee() {
 fn register_product_with_flexible_terms() {
     let product = register_product(RegisterProductCommand {
         id: "product_with_fixed_fee".to_string(),
-        terms: TermsDto::Flexible,
+        terms: TermsDto::Flexible(None),
         ..Default::default()
     });
 
-    assert_eq!(product.terms, Terms::Flexible);
+    assert_eq!(
+        product.terms,
+        Terms::Flexible(FlexibleProductTerms { unbonding_period: None })
+    );
 }
 
 #[test]
@@ -246,6 +252,56 @@ fn assert_cap_more_than_max() {
     generate_product().assert_cap(500_000_000_000);
 }
 
+#[test]
+fn quote_product_before_and_after_maturity() {
+    let product = generate_product();
+    let mut context = Context::new(accounts(0)).with_products(&[product.clone()]);
+
+    context.set_block_timestamp_in_days(10);
+
+    let half_term = context
+        .contract
+        .quote_product(product.id.clone(), U128(100_000_000), U64(MS_IN_YEAR / 2));
+    assert_eq!(6_000_000, half_term.0);
+
+    let double_term = context
+        .contract
+        .quote_product(product.id, U128(100_000_000), U64(MS_IN_YEAR * 2));
+    assert_eq!(12_000_000, double_term.0);
+}
+
+#[test]
+#[should_panic(expected = "Total amount is out of product bounds: [100..100000000000]")]
+fn quote_product_with_amount_out_of_bounds() {
+    let product = generate_product();
+    let context = Context::new(accounts(0)).with_products(&[product.clone()]);
+
+    context.contract.quote_product(product.id, U128(10), U64(MS_IN_YEAR));
+}
+
+#[test]
+#[should_panic(expected = "Quote duration is too long")]
+fn quote_product_with_overflowing_duration() {
+    let product = generate_product();
+    let mut context = Context::new(accounts(0)).with_products(&[product.clone()]);
+
+    context.set_block_timestamp_in_days(10);
+
+    context
+        .contract
+        .quote_product(product.id, U128(100_000_000), U64(u64::MAX));
+}
+
+#[test]
+fn flexible_terms_view_keeps_its_shape_with_unbonding_period() {
+    let product = Product::generate("product").unbonding_period(MS_IN_YEAR);
+
+    let view = to_value(ProductView::from(product)).unwrap();
+
+    assert_eq!(json!({ "type": "flexible" }), view["terms"]);
+    assert_eq!(json!(MS_IN_YEAR.to_string()), view["unbonding_period"]);
+}
+
 fn generate_product() -> Product {
     Product::generate("product")
         .enabled(true)
@@ -18,10 +18,16 @@ pub struct ProductView {
     pub terms: TermsView,
     pub withdrawal_fee: Option<WithdrawalFeeView>,
     pub is_enabled: bool,
+    pub is_transferable: bool,
+    /// The unbonding period of a Flexible product. It's kept out of `terms`, so the shape of `TermsView`
+    /// stays the same for existing clients.
+    pub unbonding_period: Option<U64>,
 }
 
 impl From<Product> for ProductView {
     fn from(value: Product) -> Self {
+        let unbonding_period = value.get_unbonding_period().map(U64);
+
         Self {
             id: value.id,
             apy: value.apy.into(),
@@ -29,6 +35,8 @@ impl From<Product> for ProductView {
             terms: value.terms.into(),
             withdrawal_fee: value.withdrawal_fee.map(Into::into),
             is_enabled: value.is_enabled,
+            is_transferable: value.is_transferable,
+            unbonding_period,
         }
     }
 }
@@ -56,7 +64,7 @@ impl From<Terms> for TermsView {
                 allows_top_up: value.allows_top_up,
                 allows_restaking: value.allows_restaking,
             }),
-            Terms::Flexible => TermsView::Flexible,
+            Terms::Flexible(_) => TermsView::Flexible,
         }
     }
 }
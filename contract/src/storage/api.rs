@@ -0,0 +1,316 @@
+use model::jar::JarId;
+use near_contract_standards::storage_management::{StorageBalance, StorageBalanceBounds, StorageManagement};
+use near_sdk::{
+    assert_one_yocto, env, env::panic_str, json_types::U128, near_bindgen, require, AccountId, Balance, Promise,
+    StorageUsage,
+};
+
+use crate::{
+    assert::require_ok,
+    event::{emit, EventKind, SponsorStorageData},
+    storage::model::AccountStorage,
+    Contract, ContractExt,
+};
+
+/// The `StorageSponsorApi` trait defines methods for covering storage of accounts which don't hold NEAR,
+/// e.g. accounts onboarded by a relayer.
+pub trait StorageSponsorApi {
+    /// Grants an allowance covering storage of the account. The allowance is equal to the attached deposit.
+    /// Unlike a regular storage deposit, the allowance can't be withdrawn by the account.
+    /// The account is registered if it isn't registered yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The `AccountId` of the sponsored account.
+    ///
+    /// # Returns
+    ///
+    /// A `StorageBalance` of the account after the allowance is granted.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if it's called by an account other than the manager or if no deposit is attached.
+    fn sponsor_storage(&mut self, account_id: AccountId) -> StorageBalance;
+}
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+    /// Registers an account or increases its storage deposit by the attached amount.
+    /// If `registration_only` is set, a deposit above the minimum balance is refunded.
+    #[payable]
+    fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let registration_only = registration_only.unwrap_or(false);
+
+        let refund = if let Some(storage) = self.storage_accounts.get_mut(&account_id) {
+            if registration_only {
+                amount
+            } else {
+                storage.deposit += amount;
+                0
+            }
+        } else {
+            let min_balance = self.storage_balance_bounds().min.0;
+            require!(
+                amount >= min_balance,
+                format!("The attached deposit is less than the minimum storage balance: {min_balance}")
+            );
+
+            let deposit = if registration_only { min_balance } else { amount };
+            self.register_storage(&account_id).deposit = deposit;
+
+            amount - deposit
+        };
+
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+
+        self.get_storage_balance(&account_id)
+    }
+
+    /// Withdraws the part of the storage deposit which isn't used by jars of the caller.
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let storage = self.get_account_storage_mut(&account_id);
+        let available = storage.available();
+        let amount = amount.map_or(available, |amount| amount.0);
+
+        require!(
+            amount <= available,
+            format!("The amount is greater than the available storage balance: {available}")
+        );
+
+        storage.deposit -= amount;
+
+        if amount > 0 {
+            Promise::new(account_id.clone()).transfer(amount);
+        }
+
+        self.get_storage_balance(&account_id)
+    }
+
+    /// Unregisters the caller and refunds the storage deposit. The sponsored allowance isn't refunded.
+    /// Jars can't be burned, so the caller must not have any jars even if `force` is set. An account which
+    /// has ever had jars stays registered with the minimum balance covering its last jar ID, which protects
+    /// signed tickets from being reused.
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+
+        let _ = force;
+        let account_id = env::predecessor_account_id();
+
+        if !self.storage_accounts.contains_key(&account_id) {
+            return false;
+        }
+
+        require!(
+            self.account_jars
+                .get(&account_id)
+                .map_or(true, |account_jars| account_jars.jar_ids.is_empty()),
+            "Can't unregister an account with jars"
+        );
+        require!(
+            !self.account_jars.contains_key(&account_id) && !self.last_jar_ids.contains_key(&account_id),
+            "Can't unregister an account which has had jars"
+        );
+
+        self.refund_storage(&account_id);
+
+        true
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(Balance::from(self.account_storage_usage) * env::storage_byte_cost()),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_accounts.get(&account_id).map(Into::into)
+    }
+}
+
+#[near_bindgen]
+impl StorageSponsorApi for Contract {
+    #[payable]
+    fn sponsor_storage(&mut self, account_id: AccountId) -> StorageBalance {
+        self.assert_manager();
+
+        let amount = env::attached_deposit();
+        require!(amount > 0, "Attached deposit must be positive");
+
+        self.register_storage(&account_id).sponsored += amount;
+
+        emit(EventKind::SponsorStorage(SponsorStorageData {
+            account_id: account_id.clone(),
+            amount: U128(amount),
+        }));
+
+        self.get_storage_balance(&account_id)
+    }
+}
+
+impl Contract {
+    /// Writes pending changes of jars and operators to the storage and returns the storage usage of the contract.
+    pub(crate) fn flush_storage_usage(&mut self) -> StorageUsage {
+        self.operators.flush();
+        self.account_jars.flush();
+        self.jars.flush();
+        self.jar_history.flush();
+        self.last_jar_ids.flush();
+
+        env::storage_usage()
+    }
+
+    /// Charges an account for the storage used since `initial_usage` was taken with `flush_storage_usage`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the storage grew and the account isn't registered
+    /// or its storage balance doesn't cover the growth.
+    pub(crate) fn charge_storage(&mut self, account_id: &AccountId, initial_usage: StorageUsage) {
+        let usage = self.flush_storage_usage();
+
+        if usage <= initial_usage {
+            return;
+        }
+
+        let storage = self.get_account_storage_mut(account_id);
+        storage.used_bytes += usage - initial_usage;
+
+        require_ok(storage.check_covered());
+    }
+
+    /// Adds the storage used since `initial_usage` was taken with `flush_storage_usage` to an account
+    /// without requiring the account to cover it. It's used for bounded growth of existing jars, e.g. top-ups,
+    /// history and penalties, which also happens in callbacks and privileged calls. An account which doesn't
+    /// cover its storage can't get new jars until it deposits more.
+    pub(crate) fn charge_storage_unchecked(&mut self, account_id: &AccountId, initial_usage: StorageUsage) {
+        let usage = self.flush_storage_usage();
+
+        if let Some(storage) = self.storage_accounts.get_mut(account_id) {
+            storage.used_bytes += usage.saturating_sub(initial_usage);
+        }
+    }
+
+    /// Covers the storage of jars moved from the previous layout with a sponsored allowance.
+    /// This storage used to be paid by the contract, so migrated accounts don't need a storage deposit.
+    pub(crate) fn sponsor_migrated_storage(&mut self, account_id: &AccountId, initial_usage: StorageUsage) {
+        let usage = self.flush_storage_usage();
+        let account_storage_usage = self.account_storage_usage;
+        let is_registered = self.storage_accounts.contains_key(account_id);
+
+        let migrated_bytes = usage.saturating_sub(initial_usage);
+        let sponsored_bytes = if is_registered {
+            migrated_bytes
+        } else {
+            migrated_bytes + account_storage_usage
+        };
+
+        let storage = self.register_storage(account_id);
+        storage.used_bytes += migrated_bytes;
+        storage.sponsored += Balance::from(sponsored_bytes) * env::storage_byte_cost();
+    }
+
+    /// Returns the storage released since `initial_usage` was taken with `flush_storage_usage` to an account.
+    pub(crate) fn release_storage(&mut self, account_id: &AccountId, initial_usage: StorageUsage) {
+        let usage = self.flush_storage_usage();
+        let account_storage_usage = self.account_storage_usage;
+
+        if let Some(storage) = self.storage_accounts.get_mut(account_id) {
+            let released = initial_usage.saturating_sub(usage);
+            storage.used_bytes = storage.used_bytes.saturating_sub(released).max(account_storage_usage);
+        }
+    }
+
+    /// Unregisters an account and refunds its storage deposit. Returns the refunded amount.
+    pub(crate) fn refund_storage(&mut self, account_id: &AccountId) -> Balance {
+        let Some(storage) = self.storage_accounts.remove(account_id) else {
+            return 0;
+        };
+
+        if storage.deposit > 0 {
+            Promise::new(account_id.clone()).transfer(storage.deposit);
+        }
+
+        storage.deposit
+    }
+
+    /// Resets the storage usage of an account without jars to the registration, which covers its last jar ID,
+    /// and refunds the rest of the storage deposit. The account stays registered. Returns the refunded amount.
+    pub(crate) fn refund_storage_above_minimum(&mut self, account_id: &AccountId) -> Balance {
+        let account_storage_usage = self.account_storage_usage;
+        let Some(storage) = self.storage_accounts.get_mut(account_id) else {
+            return 0;
+        };
+
+        storage.used_bytes = account_storage_usage;
+        let available = storage.available();
+        storage.deposit -= available;
+
+        if available > 0 {
+            Promise::new(account_id.clone()).transfer(available);
+        }
+
+        available
+    }
+
+    /// Measures the storage used by a registration of an account with the longest possible ID.
+    /// It includes the last jar ID which is kept after the account is cleaned up, so the minimum
+    /// storage balance covers it.
+    pub(crate) fn measure_account_storage_usage(&mut self) {
+        let initial_usage = env::storage_usage();
+        let account_id = AccountId::new_unchecked("a".repeat(64));
+
+        self.storage_accounts
+            .insert(account_id.clone(), AccountStorage::default());
+        self.storage_accounts.flush();
+        self.last_jar_ids.insert(account_id.clone(), JarId::MAX);
+        self.last_jar_ids.flush();
+
+        self.account_storage_usage = env::storage_usage() - initial_usage;
+
+        self.storage_accounts.remove(&account_id);
+        self.storage_accounts.flush();
+        self.last_jar_ids.remove(&account_id);
+        self.last_jar_ids.flush();
+    }
+
+    fn register_storage(&mut self, account_id: &AccountId) -> &mut AccountStorage {
+        let account_storage_usage = self.account_storage_usage;
+
+        self.storage_accounts
+            .entry(account_id.clone())
+            .or_insert_with(|| AccountStorage {
+                used_bytes: account_storage_usage,
+                ..AccountStorage::default()
+            })
+    }
+
+    fn get_account_storage_mut(&mut self, account_id: &AccountId) -> &mut AccountStorage {
+        self.storage_accounts
+            .get_mut(account_id)
+            .unwrap_or_else(|| panic_str(&format!("Account '{account_id}' isn't registered for storage")))
+    }
+
+    fn get_storage_balance(&self, account_id: &AccountId) -> StorageBalance {
+        self.storage_balance_of(account_id.clone())
+            .unwrap_or_else(|| panic_str(&format!("Account '{account_id}' isn't registered for storage")))
+    }
+}
+
+impl From<&AccountStorage> for StorageBalance {
+    fn from(value: &AccountStorage) -> Self {
+        Self {
+            total: U128(value.total()),
+            available: U128(value.available()),
+        }
+    }
+}
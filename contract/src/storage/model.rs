@@ -0,0 +1,46 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env, Balance, StorageUsage,
+};
+
+/// The `AccountStorage` struct describes NEAR tokens which cover contract storage used by an account, see NEP-145.
+#[derive(Default, BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct AccountStorage {
+    /// The amount of NEAR deposited with `storage_deposit`. It can be withdrawn as long as it isn't used.
+    pub deposit: Balance,
+
+    /// The amount of NEAR granted by the manager to cover storage of the account. It can't be withdrawn.
+    pub sponsored: Balance,
+
+    /// The number of bytes used by the account registration and jars of the account.
+    pub used_bytes: StorageUsage,
+}
+
+impl AccountStorage {
+    pub(crate) fn total(&self) -> Balance {
+        self.deposit + self.sponsored
+    }
+
+    pub(crate) fn used(&self) -> Balance {
+        Balance::from(self.used_bytes) * env::storage_byte_cost()
+    }
+
+    /// Returns the part of the deposit which isn't used. The sponsored allowance is spent first.
+    pub(crate) fn available(&self) -> Balance {
+        let used_from_deposit = self.used().saturating_sub(self.sponsored);
+
+        self.deposit.saturating_sub(used_from_deposit)
+    }
+
+    pub(crate) fn check_covered(&self) -> Result<(), String> {
+        if self.used() > self.total() {
+            return Err(format!(
+                "Not enough storage balance: {} is required, {} is deposited",
+                self.used(),
+                self.total()
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+#![cfg(test)]
+
+use model::U32;
+use near_contract_standards::storage_management::StorageManagement;
+use near_sdk::{json_types::U128, test_utils::accounts, AccountId, ONE_NEAR};
+
+use crate::{
+    claim::api::ClaimApi,
+    common::{tests::Context, MS_IN_YEAR},
+    jar::{
+        api::JarApi,
+        model::{Jar, JarTicket},
+    },
+    product::model::Product,
+    storage::api::StorageSponsorApi,
+    withdraw::api::WithdrawApi,
+};
+
+fn prepare_context() -> (AccountId, AccountId, Product, Context) {
+    let alice = accounts(0);
+    let admin = accounts(1);
+
+    let product = Product::generate("product").enabled(true).lockup_term(MS_IN_YEAR);
+    let context = Context::new(admin.clone()).with_products(&[product.clone()]);
+
+    (alice, admin, product, context)
+}
+
+fn create_jar(context: &mut Context, account_id: &AccountId, product: &Product) -> Jar {
+    let ticket = JarTicket {
+        product_id: product.id.clone(),
+        valid_until: 0.into(),
+    };
+
+    let jar = context
+        .contract
+        .create_jar(account_id.clone(), ticket, U128(product.cap.min), None);
+
+    context.contract.get_jar_internal(account_id, jar.id.0).clone()
+}
+
+#[test]
+fn storage_deposit_and_withdraw() {
+    let (alice, _, _, mut context) = prepare_context();
+
+    let min_balance = context.contract.storage_balance_bounds().min.0;
+    assert!(min_balance > 0);
+
+    context.switch_account(&alice);
+    let balance = context.with_deposit_yocto(ONE_NEAR, |context| context.contract.storage_deposit(None, None));
+    assert_eq!(ONE_NEAR, balance.total.0);
+    assert_eq!(ONE_NEAR - min_balance, balance.available.0);
+
+    let balance = context.with_deposit_yocto(1, |context| context.contract.storage_withdraw(Some(U128(1_000))));
+    assert_eq!(ONE_NEAR - 1_000, balance.total.0);
+
+    let balance = context.with_deposit_yocto(1, |context| context.contract.storage_withdraw(None));
+    assert_eq!(min_balance, balance.total.0);
+    assert_eq!(0, balance.available.0);
+}
+
+#[test]
+fn storage_deposit_registration_only() {
+    let (alice, _, _, mut context) = prepare_context();
+
+    let min_balance = context.contract.storage_balance_bounds().min.0;
+
+    let balance = context.with_deposit_yocto(ONE_NEAR, |context| {
+        context.contract.storage_deposit(Some(alice.clone()), Some(true))
+    });
+    assert_eq!(min_balance, balance.total.0);
+
+    let balance = context.with_deposit_yocto(ONE_NEAR, |context| {
+        context.contract.storage_deposit(Some(alice.clone()), Some(true))
+    });
+    assert_eq!(min_balance, balance.total.0);
+}
+
+#[test]
+#[should_panic(expected = "The attached deposit is less than the minimum storage balance")]
+fn storage_deposit_below_minimum() {
+    let (alice, _, _, mut context) = prepare_context();
+
+    context.with_deposit_yocto(1, |context| context.contract.storage_deposit(Some(alice), None));
+}
+
+#[test]
+fn create_jar_charges_storage() {
+    let (alice, _, product, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    let initial_balance = context.contract.storage_balance_of(alice.clone()).unwrap();
+
+    create_jar(&mut context, &alice, &product);
+
+    let balance = context.contract.storage_balance_of(alice.clone()).unwrap();
+    assert_eq!(initial_balance.total, balance.total);
+    assert!(balance.available.0 < initial_balance.available.0);
+
+    let jar = create_jar(&mut context, &alice, &product);
+    context.contract.delete_jar(&alice, jar.id);
+
+    let balance_after_deletion = context.contract.storage_balance_of(alice).unwrap();
+    assert_eq!(balance.available, balance_after_deletion.available);
+}
+
+#[test]
+#[should_panic(expected = "Account 'alice' isn't registered for storage")]
+fn create_jar_for_not_registered_account() {
+    let (alice, _, product, mut context) = prepare_context();
+
+    create_jar(&mut context, &alice, &product);
+}
+
+#[test]
+#[should_panic(expected = "Not enough storage balance")]
+fn create_jar_with_minimum_storage_balance() {
+    let (alice, _, product, mut context) = prepare_context();
+
+    let min_balance = context.contract.storage_balance_bounds().min.0;
+    context.with_deposit_yocto(min_balance, |context| {
+        context.contract.storage_deposit(Some(alice.clone()), None)
+    });
+
+    create_jar(&mut context, &alice, &product);
+}
+
+#[test]
+fn create_jar_with_sponsored_storage() {
+    let (alice, admin, product, mut context) = prepare_context();
+
+    context.switch_account(&admin);
+    let balance = context.with_deposit_yocto(ONE_NEAR, |context| context.contract.sponsor_storage(alice.clone()));
+    assert_eq!(ONE_NEAR, balance.total.0);
+    assert_eq!(0, balance.available.0);
+
+    create_jar(&mut context, &alice, &product);
+
+    let balance = context.contract.storage_balance_of(alice).unwrap();
+    assert_eq!(ONE_NEAR, balance.total.0);
+}
+
+#[test]
+#[should_panic(expected = "Can be performed only by admin")]
+fn sponsor_storage_by_not_admin() {
+    let (alice, _, _, mut context) = prepare_context();
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(ONE_NEAR, |context| context.contract.sponsor_storage(alice.clone()));
+}
+
+#[test]
+#[should_panic(expected = "The amount is greater than the available storage balance")]
+fn storage_withdraw_used_balance() {
+    let (alice, _, product, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    create_jar(&mut context, &alice, &product);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.storage_withdraw(Some(U128(ONE_NEAR))));
+}
+
+#[test]
+fn storage_unregister() {
+    let (alice, _, _, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    context.switch_account(&alice);
+    assert!(context.with_deposit_yocto(1, |context| context.contract.storage_unregister(None)));
+    assert!(context.contract.storage_balance_of(alice).is_none());
+    assert!(!context.with_deposit_yocto(1, |context| context.contract.storage_unregister(None)));
+}
+
+#[test]
+#[should_panic(expected = "Can't unregister an account with jars")]
+fn storage_unregister_with_jars() {
+    let (alice, _, product, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    create_jar(&mut context, &alice, &product);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.storage_unregister(Some(true)));
+}
+
+#[test]
+fn cleanup_account_after_last_jar_is_withdrawn() {
+    let (alice, _, product, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    let jar = create_jar(&mut context, &alice, &product);
+
+    context.set_block_timestamp_in_ms(MS_IN_YEAR + 1);
+    context.switch_account(&alice);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
+    context.contract.claim_total();
+
+    assert!(!context.contract.account_jars.contains_key(&alice));
+    assert_eq!(Some(jar.id), context.contract.get_last_jar_id(&alice));
+
+    let balance = context.contract.storage_balance_of(alice.clone()).unwrap();
+    assert_eq!(context.contract.storage_balance_bounds().min, balance.total);
+    assert_eq!(0, balance.available.0);
+}
+
+#[test]
+#[should_panic(expected = "Can't unregister an account which has had jars")]
+fn storage_unregister_after_cleanup_account() {
+    let (alice, _, product, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    let jar = create_jar(&mut context, &alice, &product);
+    context.contract.delete_jar(&alice, jar.id);
+    context.contract.cleanup_account(&alice);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.storage_unregister(Some(true)));
+}
+
+#[test]
+fn last_jar_id_is_restored_for_new_jars() {
+    let (alice, _, product, context) = prepare_context();
+    let mut context = context.with_storage_deposit(&[alice.clone()]);
+
+    let jar = create_jar(&mut context, &alice, &product);
+    context.contract.delete_jar(&alice, jar.id);
+    context.contract.cleanup_account(&alice);
+
+    assert!(!context.contract.account_jars.contains_key(&alice));
+    assert_eq!(Some(jar.id), context.contract.get_last_jar_id(&alice));
+
+    context.contract.account_jars_entry(&alice);
+
+    assert_eq!(jar.id, context.contract.account_jars.get(&alice).unwrap().last_id);
+    assert!(!context.contract.last_jar_ids.contains_key(&alice));
+}
+
+#[test]
+#[should_panic(expected = "Account 'charlie' isn't registered for storage")]
+fn transfer_jar_to_not_registered_account() {
+    let (alice, _, _, context) = prepare_context();
+    let charlie = accounts(2);
+
+    let product = Product::generate("transferable_product")
+        .enabled(true)
+        .lockup_term(MS_IN_YEAR)
+        .transferable(true);
+    let jar = Jar::generate(0, &alice, &product.id).principal(1_000_000);
+    let mut context = context.with_products(&[product]).with_jars(&[jar.clone()]);
+
+    context.switch_account(&alice);
+    context.with_deposit_yocto(1, |context| context.contract.transfer_jar(U32(jar.id), charlie));
+}
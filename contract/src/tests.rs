@@ -111,7 +111,7 @@ fn get_total_interest_with_single_jar_after_30_minutes() {
         .with_products(&[reference_product])
         .with_jars(&[jar.clone()]);
 
-    let contract_jar = JarView::from(context.contract.account_jars.get(&alice).unwrap().get_jar(jar_id));
+    let contract_jar = JarView::from(context.contract.get_jar_internal(&alice, jar_id));
     assert_eq!(JarView::from(jar), contract_jar);
 
     context.set_block_timestamp_in_minutes(30);
@@ -135,7 +135,7 @@ fn get_total_interest_with_single_jar_on_maturity() {
         .with_products(&[reference_product])
         .with_jars(&[jar.clone()]);
 
-    let contract_jar = JarView::from(context.contract.account_jars.get(&alice).unwrap().get_jar(jar_id));
+    let contract_jar = JarView::from(context.contract.get_jar_internal(&alice, jar_id));
     assert_eq!(JarView::from(jar), contract_jar);
 
     context.set_block_timestamp_in_days(365);
@@ -164,7 +164,7 @@ fn get_total_interest_with_single_jar_after_maturity() {
         .with_products(&[reference_product])
         .with_jars(&[jar.clone()]);
 
-    let contract_jar = JarView::from(context.contract.account_jars.get(&alice).unwrap().get_jar(jar_id));
+    let contract_jar = JarView::from(context.contract.get_jar_internal(&alice, jar_id));
     assert_eq!(JarView::from(jar), contract_jar);
 
     context.set_block_timestamp_in_days(400);
@@ -186,7 +186,7 @@ fn get_total_interest_with_single_jar_after_claim_on_half_term_and_maturity() {
         .with_products(&[reference_product])
         .with_jars(&[jar.clone()]);
 
-    let contract_jar = JarView::from(context.contract.account_jars.get(&alice).unwrap().get_jar(jar_id));
+    let contract_jar = JarView::from(context.contract.get_jar_internal(&alice, jar_id));
     assert_eq!(JarView::from(jar), contract_jar);
 
     context.set_block_timestamp_in_days(182);
@@ -221,7 +221,7 @@ fn penalty_is_not_applicable_for_constant_apy() {
         .with_jars(&[reference_jar]);
 
     context.switch_account(&admin);
-    context.contract.set_penalty(alice, U32(0), true);
+    context.contract.set_penalty(alice, U32(0), true, None);
 }
 
 #[test]
@@ -249,7 +249,7 @@ fn get_total_interest_for_premium_with_penalty_after_half_term() {
     assert_eq!(interest, 10_000_000);
 
     context.switch_account(&admin);
-    context.contract.set_penalty(alice.clone(), U32(0), true);
+    context.contract.set_penalty(alice.clone(), U32(0), true, None);
 
     context.set_block_timestamp_in_ms(31_536_000_000);
 
@@ -280,13 +280,13 @@ fn get_total_interest_for_premium_with_multiple_penalties_applied() {
     context.switch_account(&admin);
 
     context.set_block_timestamp_in_ms(270_000);
-    context.contract.set_penalty(alice.clone(), U32(0), true);
+    context.contract.set_penalty(alice.clone(), U32(0), true, None);
 
     context.set_block_timestamp_in_ms(390_000);
-    context.contract.set_penalty(alice.clone(), U32(0), false);
+    context.contract.set_penalty(alice.clone(), U32(0), false, None);
 
     context.set_block_timestamp_in_ms(1_264_000);
-    context.contract.set_penalty(alice.clone(), U32(0), true);
+    context.contract.set_penalty(alice.clone(), U32(0), true, None);
 
     context.set_block_timestamp_in_ms(3_700_000);
 
@@ -343,7 +343,7 @@ fn apply_penalty_in_batch() {
 
     context
         .contract
-        .batch_set_penalty(vec![(alice.clone(), alice_jars), (bob.clone(), bob_jars)], true);
+        .batch_set_penalty(vec![(alice.clone(), alice_jars), (bob.clone(), bob_jars)], true, None);
 
     context.set_block_timestamp_in_days(365);
 
@@ -374,7 +374,7 @@ fn get_interest_after_withdraw() {
     context.set_block_timestamp_in_days(400);
 
     context.switch_account(&alice);
-    context.contract.withdraw(U32(jar.id), None);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
 
     let interest = context.contract.get_total_interest(alice.clone());
     assert_eq!(12_000_000, interest.amount.total.0);
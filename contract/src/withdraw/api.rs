@@ -1,16 +1,23 @@
 use model::{
-    jar::{JarId, JarIdView},
+    jar::{JarId, JarIdView, JarView},
     withdraw::{Fee, WithdrawView},
     TokenAmount,
 };
-use near_sdk::{ext_contract, is_promise_success, json_types::U128, near_bindgen, PromiseOrValue};
+use near_sdk::{ext_contract, is_promise_success, json_types::U128, near_bindgen, require, PromiseOrValue};
 
 use crate::{
-    assert::{assert_is_liquidable, assert_not_locked, assert_sufficient_balance},
+    assert::{
+        check_is_liquidable, check_not_frozen, check_not_locked, check_not_unbonding, check_sufficient_balance,
+        require_ok,
+    },
+    common::Timestamp,
     env,
-    event::{emit, EventKind, WithdrawData},
-    jar::model::Jar,
+    event::{emit, CancelWithdrawData, EventKind, RequestWithdrawData, WithdrawData},
+    history::model::JarActivityKind,
+    jar::model::{Jar, LockKind},
+    operator::model::OperatorAction,
     product::model::WithdrawalFee,
+    withdraw::view::PendingWithdrawView,
     AccountId, Contract, ContractExt, Product,
 };
 #[cfg(not(test))]
@@ -25,6 +32,11 @@ pub trait WithdrawApi {
     /// * `jar_id` - The ID of the deposit jar from which the withdrawal is being made.
     /// * `amount` - An optional `U128` value indicating the amount of tokens to withdraw. If `None` is provided,
     ///              the entire balance of the jar will be withdrawn.
+    /// * `account_id` - An optional `AccountId` of the jar owner. It allows an approved operator to withdraw
+    ///                  on behalf of the owner. Tokens withdrawn by an operator are always transferred to the owner.
+    /// * `receiver_id` - An optional `AccountId` of the account receiving the withdrawn tokens. Defaults to the owner.
+    /// * `msg` - An optional message. If it's provided, tokens are transferred with `ft_transfer_call`
+    ///           and the amount refunded by the receiver returns to the jar.
     ///
     /// # Returns
     ///
@@ -39,7 +51,81 @@ pub trait WithdrawApi {
     /// - If the caller is not the owner of the specified jar.
     /// - If the withdrawal amount exceeds the available balance in the jar.
     /// - If attempting to withdraw from a Fixed jar that is not yet mature.
-    fn withdraw(&mut self, jar_id: JarIdView, amount: Option<U128>) -> PromiseOrValue<WithdrawView>;
+    /// - If `account_id` is provided and the caller isn't an operator of this account allowed to withdraw.
+    /// - If `receiver_id` or `msg` is provided by an operator.
+    /// - If the owner or the receiver is blocked.
+    /// - If the amount exceeds the outflow allowance of the product within the current window.
+    fn withdraw(
+        &mut self,
+        jar_id: JarIdView,
+        amount: Option<U128>,
+        account_id: Option<AccountId>,
+        receiver_id: Option<AccountId>,
+        msg: Option<String>,
+    ) -> PromiseOrValue<WithdrawView>;
+
+    /// Requests a withdrawal from a jar of a Flexible product with an unbonding period. The requested amount
+    /// is moved out of the jar principal and stops yielding interest. It can be withdrawn with `finalize_withdraw`
+    /// after the unbonding period of the product.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the jar.
+    /// * `amount` - An optional `U128` value indicating the amount of tokens to withdraw. If `None` is provided,
+    ///              the entire principal of the jar is requested.
+    ///
+    /// # Returns
+    ///
+    /// A `PendingWithdrawView` describing the requested withdrawal.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the caller is not the owner of the specified jar or is blocked.
+    /// - If the product of the jar has no unbonding period.
+    /// - If the jar already has a pending withdrawal request, is locked or frozen.
+    /// - If the amount exceeds the principal of the jar.
+    fn request_withdraw(&mut self, jar_id: JarIdView, amount: Option<U128>) -> PendingWithdrawView;
+
+    /// Cancels a pending withdrawal request and returns the requested amount to the jar principal.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the jar.
+    ///
+    /// # Returns
+    ///
+    /// The principal of the jar after the cancellation.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the caller is blocked, or if the jar has no pending withdrawal request,
+    /// is locked or frozen.
+    fn cancel_withdraw(&mut self, jar_id: JarIdView) -> U128;
+
+    /// Transfers tokens of a pending withdrawal request to the owner of the jar once the unbonding period is over.
+    /// A withdrawal fee of the product is applied to the transferred amount. A jar left without principal
+    /// and interest is closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `jar_id` - The ID of the jar.
+    ///
+    /// # Returns
+    ///
+    /// A `PromiseOrValue<WithdrawView>` with the withdrawn amount and the fee.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic under the following conditions:
+    /// - If the caller is not the owner of the specified jar or is blocked.
+    /// - If the jar has no pending withdrawal request, is locked or frozen.
+    /// - If the unbonding period isn't over yet.
+    /// - If the amount exceeds the outflow allowance of the product within the current window.
+    fn finalize_withdraw(&mut self, jar_id: JarIdView) -> PromiseOrValue<WithdrawView>;
+
+    /// Returns pending withdrawal requests of all jars belonging to an account.
+    fn get_pending_withdrawals(&self, account_id: AccountId) -> Vec<PendingWithdrawView>;
 }
 
 #[ext_contract(ext_self)]
@@ -48,59 +134,246 @@ pub trait WithdrawCallbacks {
         &mut self,
         account_id: AccountId,
         jar_id: JarId,
+        lock_id: u64,
         close_jar: bool,
         withdrawn_amount: TokenAmount,
         fee: Option<Fee>,
+        is_transfer_call: bool,
+    ) -> WithdrawView;
+
+    fn after_finalize_withdraw(
+        &mut self,
+        account_id: AccountId,
+        jar_id: JarId,
+        lock_id: u64,
+        amount: TokenAmount,
+        fee: Option<Fee>,
     ) -> WithdrawView;
+
+    fn after_restake_withdraw(
+        &mut self,
+        account_id: AccountId,
+        jar_id: JarId,
+        lock_id: u64,
+        close_jar: bool,
+        withdrawn_amount: TokenAmount,
+        new_jar: JarView,
+    ) -> JarView;
 }
 
 #[near_bindgen]
 impl WithdrawApi for Contract {
-    fn withdraw(&mut self, jar_id: JarIdView, amount: Option<U128>) -> PromiseOrValue<WithdrawView> {
+    fn withdraw(
+        &mut self,
+        jar_id: JarIdView,
+        amount: Option<U128>,
+        account_id: Option<AccountId>,
+        receiver_id: Option<AccountId>,
+        msg: Option<String>,
+    ) -> PromiseOrValue<WithdrawView> {
+        let account_id = self.get_account_for_action(account_id, OperatorAction::WithdrawToSelf);
+        let now = env::block_timestamp_ms();
+
+        let receiver_id = self.get_transfer_receiver(&account_id, receiver_id, &msg);
+
+        let (jar, amount) = require_ok(self.check_withdraw(&account_id, jar_id.0, amount, now));
+        let jar = jar.clone();
+        let product = self.get_product(&jar.product_id);
+
+        let mut withdrawn_jar = jar.withdrawn(product, amount, now);
+        let close_jar = withdrawn_jar.should_be_closed(product, now);
+
+        let lock_id = self.increment_and_get_last_lock_id();
+        withdrawn_jar.lock(lock_id, LockKind::Withdraw, &jar, amount, now);
+        *self.get_jar_mut_internal(&jar.account_id, jar.id) = withdrawn_jar;
+
+        self.consume_outflow(&jar.product_id, amount, now);
+        self.decrease_product_tvl(&jar.product_id, amount);
+
+        self.transfer_withdraw(&account_id, &receiver_id, amount, &jar, close_jar, lock_id, msg)
+    }
+
+    fn request_withdraw(&mut self, jar_id: JarIdView, amount: Option<U128>) -> PendingWithdrawView {
         let account_id = env::predecessor_account_id();
-        let jar = self.get_jar_internal(&account_id, jar_id.0).clone();
+        let now = env::block_timestamp_ms();
+
+        self.assert_not_blocked(&account_id);
+
+        let jar = self.get_jar_internal(&account_id, jar_id.0);
 
-        assert_not_locked(&jar);
+        require_ok(check_not_locked(jar).and_then(|()| check_not_frozen(jar)));
+        require_ok(check_not_unbonding(jar));
+
+        let product = self.get_product(&jar.product_id).clone();
+
+        require!(
+            product.get_unbonding_period().is_some(),
+            "The product doesn't have an unbonding period"
+        );
 
         let amount = amount.map_or(jar.principal, |value| value.0);
 
-        assert_sufficient_balance(&jar, amount);
+        require!(amount > 0, "Nothing to withdraw");
+        require_ok(check_sufficient_balance(jar, amount));
+
+        let jar = self.get_jar_mut_internal(&account_id, jar_id.0);
+        jar.request_withdraw(&product, amount, now);
 
+        let unbonding = jar.unbonding.clone().expect("Unbonding must be set");
+
+        emit(EventKind::RequestWithdraw(RequestWithdrawData {
+            id: jar_id.0,
+            amount: U128(amount),
+            available_at: unbonding.available_at,
+        }));
+
+        PendingWithdrawView::new(jar_id.0, &unbonding)
+    }
+
+    fn cancel_withdraw(&mut self, jar_id: JarIdView) -> U128 {
+        let account_id = env::predecessor_account_id();
         let now = env::block_timestamp_ms();
-        let product = self.get_product(&jar.product_id);
 
-        assert_is_liquidable(&jar, product, now);
+        self.assert_not_blocked(&account_id);
 
-        let mut withdrawn_jar = jar.withdrawn(product, amount, now);
-        let close_jar = withdrawn_jar.should_be_closed(product, now);
+        let jar = self.get_jar_internal(&account_id, jar_id.0);
 
-        withdrawn_jar.lock();
-        *self.get_jar_mut_internal(&jar.account_id, jar.id) = withdrawn_jar;
+        require_ok(check_not_locked(jar).and_then(|()| check_not_frozen(jar)));
+        require!(
+            jar.is_unbonding(),
+            format!("Jar with id: {} has no pending withdrawal request", jar.id)
+        );
+
+        let product = self.get_product(&jar.product_id).clone();
+        let jar = self.get_jar_mut_internal(&account_id, jar_id.0);
+        let amount = jar.cancel_withdraw(&product, now);
+        let principal = jar.principal;
+
+        emit(EventKind::CancelWithdraw(CancelWithdrawData {
+            id: jar_id.0,
+            amount: U128(amount),
+        }));
+
+        U128(principal)
+    }
+
+    fn finalize_withdraw(&mut self, jar_id: JarIdView) -> PromiseOrValue<WithdrawView> {
+        let account_id = env::predecessor_account_id();
+        let now = env::block_timestamp_ms();
+
+        self.assert_not_blocked(&account_id);
+
+        let jar = self.get_jar_internal(&account_id, jar_id.0).clone();
+
+        require_ok(check_not_locked(&jar).and_then(|()| check_not_frozen(&jar)));
 
-        self.transfer_withdraw(&account_id, amount, &jar, close_jar)
+        let Some(unbonding) = jar.unbonding.clone() else {
+            env::panic_str(&format!("Jar with id: {} has no pending withdrawal request", jar.id));
+        };
+
+        require!(now >= unbonding.available_at, "The unbonding period isn't over yet");
+
+        // The withdrawal fee is charged only for the unbonded part of the principal
+        let unbonded_part = Jar {
+            principal: unbonding.amount,
+            ..jar.clone()
+        };
+        let fee = self.get_fee(self.get_product(&jar.product_id), &unbonded_part);
+
+        let lock_id = self.increment_and_get_last_lock_id();
+        self.get_jar_mut_internal(&account_id, jar.id)
+            .lock(lock_id, LockKind::Withdraw, &jar, unbonding.amount, now);
+
+        self.consume_outflow(&jar.product_id, unbonding.amount, now);
+        self.decrease_product_tvl(&jar.product_id, unbonding.amount);
+
+        self.transfer_unbonded(&account_id, jar.id, unbonding.amount, fee, lock_id)
+    }
+
+    fn get_pending_withdrawals(&self, account_id: AccountId) -> Vec<PendingWithdrawView> {
+        self.account_jars(&account_id)
+            .into_iter()
+            .filter_map(|jar| {
+                jar.unbonding
+                    .as_ref()
+                    .map(|unbonding| PendingWithdrawView::new(jar.id, unbonding))
+            })
+            .collect()
     }
 }
 
 impl Contract {
+    /// Checks whether the amount can be withdrawn from a jar. Returns the jar and the amount to withdraw if it can.
+    pub(crate) fn check_withdraw(
+        &self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        amount: Option<U128>,
+        now: Timestamp,
+    ) -> Result<(&Jar, TokenAmount), String> {
+        self.check_not_blocked(account_id)?;
+
+        let jar = self.find_jar(account_id, jar_id)?;
+
+        check_not_locked(jar)?;
+        check_not_frozen(jar)?;
+
+        let amount = amount.map_or(jar.principal, |value| value.0);
+        let product = self.find_product(&jar.product_id)?;
+
+        if product.get_unbonding_period().is_some() {
+            return Err(format!(
+                "Jar with id: {} has an unbonding period, request the withdrawal first",
+                jar.id
+            ));
+        }
+
+        check_sufficient_balance(jar, amount)?;
+        check_is_liquidable(jar, product, now)?;
+        self.check_outflow(&jar.product_id, amount, now)?;
+
+        Ok((jar, amount))
+    }
+
+    /// Completes a withdrawal. `refund` is the amount returned by the receiver of `ft_transfer_call`,
+    /// it goes back to the jar principal. Nothing is changed if the jar isn't held by the lock of the withdrawal.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn after_withdraw_internal(
         &mut self,
         account_id: AccountId,
         jar_id: JarId,
+        lock_id: u64,
         close_jar: bool,
         withdrawn_amount: TokenAmount,
         fee: Option<Fee>,
         is_promise_success: bool,
+        refund: TokenAmount,
     ) -> WithdrawView {
+        if !self.is_locked_by(jar_id, lock_id) {
+            return WithdrawView::new(0, None);
+        }
+
         if !is_promise_success {
-            let jar = self.get_jar_mut_internal(&account_id, jar_id);
-            jar.principal += withdrawn_amount;
-            jar.unlock();
+            self.revert_withdrawal(&account_id, jar_id, withdrawn_amount);
 
             return WithdrawView::new(0, None);
         }
 
+        let product_id = self.get_jar_internal(&account_id, jar_id).product_id.clone();
+        let withdrawn_amount = withdrawn_amount - refund;
+        let close_jar = close_jar && refund == 0;
+
+        if refund > 0 {
+            self.get_jar_mut_internal(&account_id, jar_id).principal += refund;
+
+            self.release_outflow(&product_id, refund);
+            self.increase_product_tvl(&product_id, refund);
+        }
+
+        self.record_activity(jar_id, JarActivityKind::Withdraw, withdrawn_amount);
+
         if close_jar {
-            self.delete_jar(&account_id, jar_id);
+            self.close_jar(&account_id, jar_id);
         } else {
             self.get_jar_mut_internal(&account_id, jar_id).unlock();
         }
@@ -116,7 +389,97 @@ impl Contract {
         withdrawal_result
     }
 
-    fn get_fee(&self, product: &Product, jar: &Jar) -> Option<Fee> {
+    /// Completes the transfer of the part of the principal withdrawn during a partial restake.
+    /// The withdrawal is already reported by the `Restake` event and activity, so nothing is emitted
+    /// or recorded here. Nothing is changed if the jar isn't held by the lock of the withdrawal.
+    pub(crate) fn after_restake_withdraw_internal(
+        &mut self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        lock_id: u64,
+        close_jar: bool,
+        withdrawn_amount: TokenAmount,
+        is_promise_success: bool,
+    ) {
+        if !self.is_locked_by(jar_id, lock_id) {
+            return;
+        }
+
+        if !is_promise_success {
+            self.revert_withdrawal(account_id, jar_id, withdrawn_amount);
+        } else if close_jar {
+            self.close_jar(account_id, jar_id);
+        } else {
+            self.get_jar_mut_internal(account_id, jar_id).unlock();
+        }
+    }
+
+    /// Returns the amount of a failed transfer to the jar principal and unlocks the jar.
+    fn revert_withdrawal(&mut self, account_id: &AccountId, jar_id: JarId, withdrawn_amount: TokenAmount) {
+        let jar = self.get_jar_mut_internal(account_id, jar_id);
+        jar.principal += withdrawn_amount;
+        jar.unlock();
+
+        let product_id = jar.product_id.clone();
+
+        self.release_outflow(&product_id, withdrawn_amount);
+        self.increase_product_tvl(&product_id, withdrawn_amount);
+    }
+
+    /// Completes a withdrawal of unbonded principal. Nothing is changed if the jar isn't held by the lock
+    /// of the withdrawal.
+    pub(crate) fn after_finalize_withdraw_internal(
+        &mut self,
+        account_id: AccountId,
+        jar_id: JarId,
+        lock_id: u64,
+        amount: TokenAmount,
+        fee: Option<Fee>,
+        is_promise_success: bool,
+    ) -> WithdrawView {
+        if !self.is_locked_by(jar_id, lock_id) {
+            return WithdrawView::new(0, None);
+        }
+
+        let jar = self.get_jar_mut_internal(&account_id, jar_id);
+        jar.unlock();
+
+        if !is_promise_success {
+            let product_id = jar.product_id.clone();
+
+            self.release_outflow(&product_id, amount);
+            self.increase_product_tvl(&product_id, amount);
+
+            return WithdrawView::new(0, None);
+        }
+
+        self.get_jar_mut_internal(&account_id, jar_id).unbonding = None;
+
+        self.record_activity(jar_id, JarActivityKind::Withdraw, amount);
+
+        let now = env::block_timestamp_ms();
+        let jar = self.get_jar_internal(&account_id, jar_id);
+        let product = self.get_product(&jar.product_id);
+
+        // Flexible jars are kept open when they're emptied, but a jar fully unbonded by its owner isn't used anymore
+        let close_jar = jar.should_be_closed(product, now) || (jar.is_empty() && jar.get_interest(product, now) == 0);
+
+        if close_jar {
+            self.close_jar(&account_id, jar_id);
+        }
+
+        let withdrawal_result = WithdrawView::new(amount, fee);
+
+        emit(EventKind::FinalizeWithdraw(WithdrawData {
+            id: jar_id,
+            withdrawn_amount: withdrawal_result.withdrawn_amount,
+            fee_amount: withdrawal_result.fee,
+        }));
+
+        withdrawal_result
+    }
+
+    pub(crate) fn get_fee(&self, product: &Product, jar: &Jar) -> Option<Fee> {
         let fee = product.withdrawal_fee.as_ref()?;
 
         let amount = match fee {
@@ -134,49 +497,128 @@ impl Contract {
 #[cfg(not(test))]
 #[mutants::skip] // Covered by integration tests
 impl Contract {
-    fn transfer_withdraw(
+    pub(crate) fn transfer_withdraw(
         &mut self,
         account_id: &AccountId,
+        receiver_id: &AccountId,
         amount: TokenAmount,
         jar: &Jar,
         close_jar: bool,
+        lock_id: u64,
+        msg: Option<String>,
     ) -> PromiseOrValue<WithdrawView> {
         let product = self.get_product(&jar.product_id);
         let fee = self.get_fee(product, jar);
+        let is_transfer_call = msg.is_some();
 
         self.ft_contract()
-            .transfer(account_id, amount, "withdraw", &fee)
+            .transfer(receiver_id, amount, "withdraw", &fee, msg)
             .then(Self::after_withdraw_call(
                 account_id.clone(),
                 jar.id,
+                lock_id,
                 close_jar,
                 amount,
                 &fee,
+                is_transfer_call,
             ))
             .into()
     }
 
+    /// Transfers the part of the principal which is withdrawn during a partial restake.
+    /// The promise resolves to the view of the new jar.
+    pub(crate) fn transfer_restake_withdraw(
+        &mut self,
+        account_id: &AccountId,
+        amount: TokenAmount,
+        jar: &Jar,
+        close_jar: bool,
+        lock_id: u64,
+        new_jar: JarView,
+    ) -> PromiseOrValue<JarView> {
+        let product = self.get_product(&jar.product_id);
+        let fee = self.get_fee(product, jar);
+
+        self.ft_contract()
+            .transfer(account_id, amount, "withdraw", &fee, None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(crate::common::gas_data::GAS_FOR_AFTER_WITHDRAW)
+                    .after_restake_withdraw(account_id.clone(), jar.id, lock_id, close_jar, amount, new_jar),
+            )
+            .into()
+    }
+
+    fn transfer_unbonded(
+        &mut self,
+        account_id: &AccountId,
+        jar_id: JarId,
+        amount: TokenAmount,
+        fee: Option<Fee>,
+        lock_id: u64,
+    ) -> PromiseOrValue<WithdrawView> {
+        self.ft_contract()
+            .transfer(account_id, amount, "withdraw", &fee, None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(crate::common::gas_data::GAS_FOR_AFTER_WITHDRAW)
+                    .after_finalize_withdraw(account_id.clone(), jar_id, lock_id, amount, fee),
+            )
+            .into()
+    }
+
     fn after_withdraw_call(
         account_id: AccountId,
         jar_id: JarId,
+        lock_id: u64,
         close_jar: bool,
         withdrawn_balance: TokenAmount,
         fee: &Option<Fee>,
+        is_transfer_call: bool,
     ) -> Promise {
         ext_self::ext(env::current_account_id())
             .with_static_gas(crate::common::gas_data::GAS_FOR_AFTER_WITHDRAW)
-            .after_withdraw(account_id, jar_id, close_jar, withdrawn_balance, fee.clone())
+            .after_withdraw(
+                account_id,
+                jar_id,
+                lock_id,
+                close_jar,
+                withdrawn_balance,
+                fee.clone(),
+                is_transfer_call,
+            )
     }
 }
 
 #[cfg(test)]
 impl Contract {
-    fn transfer_withdraw(
+    fn transfer_unbonded(
         &mut self,
         account_id: &AccountId,
+        jar_id: JarId,
+        amount: TokenAmount,
+        fee: Option<Fee>,
+        lock_id: u64,
+    ) -> PromiseOrValue<WithdrawView> {
+        PromiseOrValue::Value(self.after_finalize_withdraw_internal(
+            account_id.clone(),
+            jar_id,
+            lock_id,
+            amount,
+            fee,
+            crate::common::test_data::get_test_future_success(),
+        ))
+    }
+
+    pub(crate) fn transfer_withdraw(
+        &mut self,
+        account_id: &AccountId,
+        _receiver_id: &AccountId,
         amount: TokenAmount,
         jar: &Jar,
         close_jar: bool,
+        lock_id: u64,
+        msg: Option<String>,
     ) -> PromiseOrValue<WithdrawView> {
         let product = self.get_product(&jar.product_id);
         let fee = self.get_fee(product, jar);
@@ -184,14 +626,37 @@ impl Contract {
         let withdrawn = self.after_withdraw_internal(
             account_id.clone(),
             jar.id,
+            lock_id,
             close_jar,
             amount,
             fee,
             crate::common::test_data::get_test_future_success(),
+            crate::common::test_data::get_test_future_refund(msg.is_some()),
         );
 
         PromiseOrValue::Value(withdrawn)
     }
+
+    pub(crate) fn transfer_restake_withdraw(
+        &mut self,
+        account_id: &AccountId,
+        amount: TokenAmount,
+        jar: &Jar,
+        close_jar: bool,
+        lock_id: u64,
+        new_jar: JarView,
+    ) -> PromiseOrValue<JarView> {
+        self.after_restake_withdraw_internal(
+            account_id,
+            jar.id,
+            lock_id,
+            close_jar,
+            amount,
+            crate::common::test_data::get_test_future_success(),
+        );
+
+        PromiseOrValue::Value(new_jar)
+    }
 }
 
 #[near_bindgen]
@@ -201,17 +666,60 @@ impl WithdrawCallbacks for Contract {
         &mut self,
         account_id: AccountId,
         jar_id: JarId,
+        lock_id: u64,
         close_jar: bool,
         withdrawn_amount: TokenAmount,
         fee: Option<Fee>,
+        is_transfer_call: bool,
     ) -> WithdrawView {
+        let refund = crate::ft_interface::get_transfer_refund(
+            withdrawn_amount - fee.as_ref().map_or(0, |fee| fee.amount),
+            is_transfer_call,
+        );
+
         self.after_withdraw_internal(
             account_id,
             jar_id,
+            lock_id,
             close_jar,
             withdrawn_amount,
             fee,
             is_promise_success(),
+            refund,
         )
     }
+
+    #[private]
+    fn after_finalize_withdraw(
+        &mut self,
+        account_id: AccountId,
+        jar_id: JarId,
+        lock_id: u64,
+        amount: TokenAmount,
+        fee: Option<Fee>,
+    ) -> WithdrawView {
+        self.after_finalize_withdraw_internal(account_id, jar_id, lock_id, amount, fee, is_promise_success())
+    }
+
+    #[private]
+    fn after_restake_withdraw(
+        &mut self,
+        account_id: AccountId,
+        jar_id: JarId,
+        lock_id: u64,
+        close_jar: bool,
+        withdrawn_amount: TokenAmount,
+        new_jar: JarView,
+    ) -> JarView {
+        self.after_restake_withdraw_internal(
+            &account_id,
+            jar_id,
+            lock_id,
+            close_jar,
+            withdrawn_amount,
+            is_promise_success(),
+        );
+
+        new_jar
+    }
 }
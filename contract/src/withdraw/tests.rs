@@ -5,8 +5,16 @@ use near_sdk::{json_types::U128, test_utils::accounts, AccountId, PromiseOrValue
 
 use crate::{
     claim::api::ClaimApi,
-    common::{test_data::set_test_future_success, tests::Context, udecimal::UDecimal, MS_IN_YEAR},
-    jar::{api::JarApi, model::Jar},
+    common::{
+        test_data::{set_test_future_refund, set_test_future_success},
+        tests::Context,
+        udecimal::UDecimal,
+        MS_IN_MINUTE, MS_IN_YEAR,
+    },
+    jar::{
+        api::JarApi,
+        model::{Jar, LockKind},
+    },
     product::model::{Apy, Product, WithdrawalFee},
     withdraw::api::WithdrawApi,
 };
@@ -28,7 +36,7 @@ fn prepare_jar(product: &Product) -> (AccountId, Jar, Context) {
 fn withdraw_locked_jar_before_maturity_by_not_owner() {
     let (_, _, mut context) = prepare_jar(&generate_product());
 
-    context.contract.withdraw(U32(0), None);
+    context.contract.withdraw(U32(0), None, None, None, None);
 }
 
 #[test]
@@ -37,7 +45,7 @@ fn withdraw_locked_jar_before_maturity_by_owner() {
     let (alice, jar, mut context) = prepare_jar(&generate_product());
 
     context.switch_account(&alice);
-    context.contract.withdraw(U32(jar.id), None);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
 }
 
 #[test]
@@ -47,7 +55,7 @@ fn withdraw_locked_jar_after_maturity_by_not_owner() {
     let (_, jar, mut context) = prepare_jar(&product);
 
     context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
-    context.contract.withdraw(U32(jar.id), None);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
 }
 
 #[test]
@@ -57,7 +65,7 @@ fn withdraw_locked_jar_after_maturity_by_owner() {
 
     context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
     context.switch_account(&alice);
-    context.contract.withdraw(U32(jar.id), None);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
 }
 
 #[test]
@@ -67,7 +75,7 @@ fn withdraw_flexible_jar_by_not_owner() {
     let (_, jar, mut context) = prepare_jar(&product);
 
     context.set_block_timestamp_in_days(1);
-    context.contract.withdraw(U32(jar.id), None);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
 }
 
 #[test]
@@ -78,7 +86,7 @@ fn withdraw_flexible_jar_by_owner_full() {
     context.set_block_timestamp_in_days(1);
     context.switch_account(&alice);
 
-    context.contract.withdraw(U32(reference_jar.id), None);
+    context.contract.withdraw(U32(reference_jar.id), None, None, None, None);
 
     let interest = context
         .contract
@@ -102,7 +110,7 @@ fn withdraw_flexible_jar_by_owner_with_sufficient_balance() {
     context.set_block_timestamp_in_days(1);
     context.switch_account(&alice);
 
-    context.contract.withdraw(U32(0), Some(U128(100_000)));
+    context.contract.withdraw(U32(0), Some(U128(100_000)), None, None, None);
     let jar = context.contract.get_jar(alice.clone(), U32(reference_jar.id));
     assert_eq!(900_000, jar.principal.0);
 }
@@ -115,7 +123,9 @@ fn withdraw_flexible_jar_by_owner_with_insufficient_balance() {
 
     context.set_block_timestamp_in_days(1);
     context.switch_account(&alice);
-    context.contract.withdraw(U32(jar.id), Some(U128(2_000_000)));
+    context
+        .contract
+        .withdraw(U32(jar.id), Some(U128(2_000_000)), None, None, None);
 }
 
 #[test]
@@ -131,7 +141,11 @@ fn dont_delete_jar_after_withdraw_with_interest_left() {
 
     let jar = context.contract.get_jar_internal(&alice, 0);
 
-    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(jar.id), Some(U128(1_000_000))) else {
+    let PromiseOrValue::Value(withdrawn) =
+        context
+            .contract
+            .withdraw(U32(jar.id), Some(U128(1_000_000)), None, None, None)
+    else {
         panic!();
     };
 
@@ -160,7 +174,11 @@ fn product_with_fixed_fee() {
     context.switch_account(&alice);
 
     let withdraw_amount = 100_000;
-    let PromiseOrValue::Value(withdraw) = context.contract.withdraw(U32(0), Some(U128(withdraw_amount))) else {
+    let PromiseOrValue::Value(withdraw) =
+        context
+            .contract
+            .withdraw(U32(0), Some(U128(withdraw_amount)), None, None, None)
+    else {
         panic!("Invalid promise type");
     };
 
@@ -185,7 +203,11 @@ fn product_with_percent_fee() {
     context.switch_account(&alice);
 
     let withdrawn_amount = 100_000;
-    let PromiseOrValue::Value(withdraw) = context.contract.withdraw(U32(0), Some(U128(withdrawn_amount))) else {
+    let PromiseOrValue::Value(withdraw) =
+        context
+            .contract
+            .withdraw(U32(0), Some(U128(withdrawn_amount)), None, None, None)
+    else {
         panic!("Invalid promise type");
     };
 
@@ -210,7 +232,8 @@ fn test_failed_withdraw_promise() {
 
     let jar_before_withdrawal = context.contract.get_jar(alice.clone(), U32(reference_jar.id));
 
-    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(0), Some(U128(100_000))) else {
+    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(U32(0), Some(U128(100_000)), None, None, None)
+    else {
         panic!()
     };
 
@@ -228,19 +251,22 @@ fn test_failed_withdraw_internal() {
     let withdrawn_amount = 1_234;
 
     let jar_view = context.contract.get_jar(alice.clone(), U32(reference_jar.id));
-    let jar = context
+    let jar = context.contract.get_jar_internal(&alice, reference_jar.id).clone();
+    context
         .contract
-        .account_jars
-        .get(&alice)
-        .unwrap()
-        .iter()
-        .next()
-        .unwrap();
-
-    let withdraw =
-        context
-            .contract
-            .after_withdraw_internal(jar.account_id.clone(), jar.id, true, withdrawn_amount, None, false);
+        .get_jar_mut_internal(&alice, jar.id)
+        .lock(1, LockKind::Withdraw, &jar, withdrawn_amount, 0);
+
+    let withdraw = context.contract.after_withdraw_internal(
+        jar.account_id.clone(),
+        jar.id,
+        1,
+        true,
+        withdrawn_amount,
+        None,
+        false,
+        0,
+    );
 
     assert_eq!(withdraw.withdrawn_amount, U128(0));
     assert_eq!(withdraw.fee, U128(0));
@@ -251,6 +277,33 @@ fn test_failed_withdraw_internal() {
     );
 }
 
+#[test]
+fn withdraw_with_transfer_call_refund() {
+    set_test_future_refund(300_000);
+
+    let product = generate_product();
+    let (alice, reference_jar, mut context) = prepare_jar(&product);
+
+    context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
+    context.switch_account(&alice);
+
+    let PromiseOrValue::Value(withdrawn) = context.contract.withdraw(
+        U32(reference_jar.id),
+        None,
+        None,
+        Some(accounts(3)),
+        Some("swap".to_string()),
+    ) else {
+        panic!("Invalid promise type");
+    };
+
+    assert_eq!(withdrawn.withdrawn_amount, U128(700_000));
+
+    let jar = context.contract.get_jar_internal(&alice, reference_jar.id);
+    assert_eq!(jar.principal, 300_000);
+    assert!(!jar.is_pending_withdraw);
+}
+
 #[test]
 #[should_panic(expected = "Another operation on this Jar is in progress")]
 fn withdraw_from_locked_jar() {
@@ -259,7 +312,7 @@ fn withdraw_from_locked_jar() {
         .lockup_term(MS_IN_YEAR);
     let mut jar = Jar::generate(0, &accounts(0), &product.id).principal(MS_IN_YEAR as u128);
 
-    jar.lock();
+    jar.lock(1, LockKind::Withdraw, &jar.clone(), 0, 0);
 
     let alice = accounts(0);
     let admin = accounts(1);
@@ -269,7 +322,7 @@ fn withdraw_from_locked_jar() {
     context.set_block_timestamp_in_ms(product.get_lockup_term().unwrap() + 1);
     context.switch_account(&alice);
 
-    _ = context.contract.withdraw(U32(0), Some(U128(100_000)));
+    _ = context.contract.withdraw(U32(0), Some(U128(100_000)), None, None, None);
 }
 
 pub(crate) fn generate_product() -> Product {
@@ -285,3 +338,134 @@ pub(crate) fn generate_product_with_fee(fee: &WithdrawalFee) -> Product {
         .enabled(true)
         .with_withdrawal_fee(fee.clone())
 }
+
+const MS_IN_DAY: u64 = 24 * 60 * MS_IN_MINUTE;
+
+fn prepare_unbonding_jar(unbonding_period: u64) -> (AccountId, Jar, Product, Context) {
+    let product = generate_flexible_product()
+        .unbonding_period(unbonding_period)
+        .apy(Apy::Constant(UDecimal::new(12, 2)));
+    let (alice, jar, context) = prepare_jar(&product);
+
+    (alice, jar, product, context)
+}
+
+#[test]
+#[should_panic(expected = "Jar with id: 0 has an unbonding period, request the withdrawal first")]
+fn withdraw_from_jar_with_unbonding_period() {
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(MS_IN_DAY);
+
+    context.switch_account(&alice);
+    context.contract.withdraw(U32(jar.id), None, None, None, None);
+}
+
+#[test]
+#[should_panic(expected = "Jar with id: 0 has a pending withdrawal request")]
+fn split_unbonding_jar() {
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(MS_IN_DAY);
+
+    context.switch_account(&alice);
+    context.contract.request_withdraw(U32(jar.id), Some(U128(400_000)));
+    context.contract.split_jar(U32(jar.id), U128(100_000));
+}
+
+#[test]
+fn request_and_finalize_withdraw() {
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(MS_IN_DAY);
+
+    context.set_block_timestamp_in_days(365);
+    context.switch_account(&alice);
+
+    let pending = context.contract.request_withdraw(U32(jar.id), Some(U128(400_000)));
+    assert_eq!(U128(400_000), pending.amount);
+    assert_eq!(365 * MS_IN_DAY + MS_IN_DAY, pending.available_at.0);
+    assert_eq!(vec![pending], context.contract.get_pending_withdrawals(alice.clone()));
+
+    let interest_at_request = context.contract.get_total_interest(alice.clone()).amount.total.0;
+    assert_eq!(120_000, interest_at_request);
+
+    context.set_block_timestamp_in_days(365 * 2);
+    let interest = context.contract.get_total_interest(alice.clone()).amount.total.0;
+    assert_eq!(interest_at_request + 72_000, interest);
+
+    let PromiseOrValue::Value(withdrawn) = context.contract.finalize_withdraw(U32(jar.id)) else {
+        panic!("Invalid promise type");
+    };
+    assert_eq!(U128(400_000), withdrawn.withdrawn_amount);
+
+    let jar = context.contract.get_jar_internal(&alice, jar.id);
+    assert_eq!(600_000, jar.principal);
+    assert!(jar.unbonding.is_none());
+    assert!(!jar.is_pending_withdraw);
+    assert!(context.contract.get_pending_withdrawals(alice).is_empty());
+}
+
+#[test]
+fn finalize_full_withdraw_closes_jar() {
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(0);
+
+    context.switch_account(&alice);
+    context.contract.request_withdraw(U32(jar.id), None);
+
+    let PromiseOrValue::Value(withdrawn) = context.contract.finalize_withdraw(U32(jar.id)) else {
+        panic!("Invalid promise type");
+    };
+    assert_eq!(U128(1_000_000), withdrawn.withdrawn_amount);
+    assert!(context.contract.get_jars_for_account(alice).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "The unbonding period isn't over yet")]
+fn finalize_withdraw_before_unbonding_period_is_over() {
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(MS_IN_DAY);
+
+    context.switch_account(&alice);
+    context.contract.request_withdraw(U32(jar.id), None);
+
+    context.set_block_timestamp_in_ms(MS_IN_DAY - 1);
+    context.contract.finalize_withdraw(U32(jar.id));
+}
+
+#[test]
+fn cancel_withdraw_request() {
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(MS_IN_DAY);
+
+    context.switch_account(&alice);
+    context.contract.request_withdraw(U32(jar.id), Some(U128(400_000)));
+
+    let principal = context.contract.cancel_withdraw(U32(jar.id));
+    assert_eq!(U128(1_000_000), principal);
+    assert!(context.contract.get_pending_withdrawals(alice.clone()).is_empty());
+
+    context.set_block_timestamp_in_days(365);
+    assert_eq!(120_000, context.contract.get_total_interest(alice).amount.total.0);
+}
+
+#[test]
+fn failed_finalize_withdraw_keeps_request() {
+    set_test_future_success(false);
+
+    let (alice, jar, _, mut context) = prepare_unbonding_jar(0);
+
+    context.switch_account(&alice);
+    context.contract.request_withdraw(U32(jar.id), None);
+
+    let PromiseOrValue::Value(withdrawn) = context.contract.finalize_withdraw(U32(jar.id)) else {
+        panic!("Invalid promise type");
+    };
+    assert_eq!(U128(0), withdrawn.withdrawn_amount);
+
+    let jar = context.contract.get_jar_internal(&alice, jar.id);
+    assert_eq!(0, jar.principal);
+    assert!(!jar.is_pending_withdraw);
+    assert_eq!(1, context.contract.get_pending_withdrawals(alice).len());
+}
+
+#[test]
+#[should_panic(expected = "The product doesn't have an unbonding period")]
+fn request_withdraw_without_unbonding_period() {
+    let (alice, jar, mut context) = prepare_jar(&generate_flexible_product());
+
+    context.switch_account(&alice);
+    context.contract.request_withdraw(U32(jar.id), None);
+}
@@ -0,0 +1,31 @@
+use model::{
+    jar::{JarId, JarIdView},
+    U32,
+};
+use near_sdk::{
+    json_types::{U128, U64},
+    serde::{Deserialize, Serialize},
+};
+
+use crate::jar::model::JarUnbonding;
+
+/// Principal requested for withdrawal from a jar which waits for the end of the unbonding period.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingWithdrawView {
+    pub jar_id: JarIdView,
+    pub amount: U128,
+    pub requested_at: U64,
+    pub available_at: U64,
+}
+
+impl PendingWithdrawView {
+    pub(crate) fn new(jar_id: JarId, unbonding: &JarUnbonding) -> Self {
+        Self {
+            jar_id: U32(jar_id),
+            amount: U128(unbonding.amount),
+            requested_at: U64(unbonding.requested_at),
+            available_at: U64(unbonding.available_at),
+        }
+    }
+}
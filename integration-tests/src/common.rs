@@ -100,6 +100,7 @@ pub(crate) async fn prepare_contract(
 
     context.ft_contract.storage_deposit(&fee_account).await?;
     context.ft_contract.storage_deposit(&alice).await?;
+    context.jar_contract.storage_deposit(&alice).await?;
     context.ft_contract.mint_for_user(&alice, 100_000_000).await?;
     context.ft_contract.mint_for_user(&manager, 100_000_000).await?;
     context
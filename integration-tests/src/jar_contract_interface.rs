@@ -63,6 +63,15 @@ pub(crate) trait JarContractInterface {
 
     async fn restake(&self, user: &Account, jar_id: JarIdView) -> anyhow::Result<()>;
 
+    async fn restake_all(&self, user: &Account, consolidate: bool) -> anyhow::Result<()>;
+
+    async fn transfer_jar(&self, user: &Account, jar_id: JarIdView, receiver_id: &AccountId)
+        -> anyhow::Result<JarView>;
+
+    async fn split_jar(&self, user: &Account, jar_id: JarIdView, amount: U128) -> anyhow::Result<JarView>;
+
+    async fn merge_jars(&self, user: &Account, jar_ids: Vec<JarIdView>) -> anyhow::Result<JarView>;
+
     async fn set_penalty(
         &self,
         admin: &Account,
@@ -91,6 +100,8 @@ pub(crate) trait JarContractInterface {
     ) -> anyhow::Result<U128>;
 
     async fn block_timestamp_ms(&self) -> anyhow::Result<Timestamp>;
+
+    async fn storage_deposit(&self, user: &Account) -> anyhow::Result<()>;
 }
 
 #[async_trait]
@@ -421,6 +432,122 @@ impl JarContractInterface for Contract {
         Ok(())
     }
 
+    async fn restake_all(&self, user: &Account, consolidate: bool) -> anyhow::Result<()> {
+        println!("▶️ Restake all jars");
+
+        let args = json!({
+            "consolidate": consolidate,
+        });
+
+        let result = user
+            .call(self.id(), "restake_all")
+            .args_json(args)
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        for log in result.logs() {
+            println!("   📖 {log}");
+        }
+
+        OutcomeStorage::add_result(result);
+
+        Ok(())
+    }
+
+    async fn transfer_jar(
+        &self,
+        user: &Account,
+        jar_id: JarIdView,
+        receiver_id: &AccountId,
+    ) -> anyhow::Result<JarView> {
+        println!("▶️ Transfer jar #{jar_id:?} to {receiver_id}");
+
+        let args = json!({
+            "jar_id": jar_id,
+            "receiver_id": receiver_id,
+        });
+
+        let result = user
+            .call(self.id(), "transfer_jar")
+            .args_json(args)
+            .max_gas()
+            .deposit(parse_near!("1 yocto"))
+            .transact()
+            .await?
+            .into_result()?;
+
+        for log in result.logs() {
+            println!("   📖 {log}");
+        }
+
+        let result_value = result.json()?;
+
+        println!("   ✅ {result_value:?}");
+
+        OutcomeStorage::add_result(result);
+
+        Ok(result_value)
+    }
+
+    async fn split_jar(&self, user: &Account, jar_id: JarIdView, amount: U128) -> anyhow::Result<JarView> {
+        println!("▶️ Split {amount:?} from jar #{jar_id:?}");
+
+        let args = json!({
+            "jar_id": jar_id,
+            "amount": amount,
+        });
+
+        let result = user
+            .call(self.id(), "split_jar")
+            .args_json(args)
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        for log in result.logs() {
+            println!("   📖 {log}");
+        }
+
+        let result_value = result.json()?;
+
+        println!("   ✅ {result_value:?}");
+
+        OutcomeStorage::add_result(result);
+
+        Ok(result_value)
+    }
+
+    async fn merge_jars(&self, user: &Account, jar_ids: Vec<JarIdView>) -> anyhow::Result<JarView> {
+        println!("▶️ Merge jars {jar_ids:?}");
+
+        let args = json!({
+            "jar_ids": jar_ids,
+        });
+
+        let result = user
+            .call(self.id(), "merge_jars")
+            .args_json(args)
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        for log in result.logs() {
+            println!("   📖 {log}");
+        }
+
+        let result_value = result.json()?;
+
+        println!("   ✅ {result_value:?}");
+
+        OutcomeStorage::add_result(result);
+
+        Ok(result_value)
+    }
+
     async fn set_penalty(
         &self,
         admin: &Account,
@@ -589,6 +716,20 @@ impl JarContractInterface for Contract {
 
         Ok(result)
     }
+
+    async fn storage_deposit(&self, user: &Account) -> anyhow::Result<()> {
+        println!("▶️ Storage deposit for {}", user.id());
+
+        user.call(self.id(), "storage_deposit")
+            .args_json(json!({}))
+            .max_gas()
+            .deposit(parse_near!("0.05 N"))
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
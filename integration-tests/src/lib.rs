@@ -13,4 +13,5 @@ mod premium_product;
 mod product;
 mod product_actions;
 mod restake;
+mod transfer;
 mod withdraw_fee;
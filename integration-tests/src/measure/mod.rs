@@ -7,6 +7,7 @@ pub(crate) mod outcome_storage;
 pub(crate) mod random_element;
 mod register_product;
 mod restake;
+mod restake_all;
 mod stake;
 mod top_up;
 pub(crate) mod utils;
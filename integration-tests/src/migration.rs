@@ -26,6 +26,8 @@ async fn migration() -> anyhow::Result<()> {
     context.ft_contract.storage_deposit(manager).await?;
     context.ft_contract.storage_deposit(alice).await?;
     context.ft_contract.storage_deposit(bob).await?;
+    context.jar_contract.storage_deposit(alice).await?;
+    context.jar_contract.storage_deposit(bob).await?;
 
     context.ft_contract.mint_for_user(manager, 3_000_000).await?;
     context.ft_contract.mint_for_user(alice, 100_000_000).await?;
@@ -117,6 +117,7 @@ impl RegisterProductCommand {
                     }
                 },
                 "is_enabled": true,
+                "is_transferable": true,
             }),
             RegisterProductCommand::Locked10Minutes60000Percents => json!({
                 "id": "flexible_6_months_60000_percents",
@@ -0,0 +1,56 @@
+use crate::{
+    common::{prepare_contract, Prepared, ValueGetters},
+    product::RegisterProductCommand,
+};
+
+#[tokio::test]
+#[mutants::skip]
+async fn transfer_jar() -> anyhow::Result<()> {
+    println!("👷🏽 Run jar transfer test");
+
+    let product_command = RegisterProductCommand::Locked10Minutes6Percents;
+
+    let Prepared {
+        mut context,
+        manager: _,
+        alice,
+        fee_account: _,
+    } = prepare_contract([product_command]).await?;
+
+    let bob = context.account("bob").await?;
+    context.jar_contract.storage_deposit(&bob).await?;
+
+    context
+        .jar_contract
+        .create_jar(
+            &alice,
+            product_command.id(),
+            1_000_000,
+            context.ft_contract.account().id(),
+        )
+        .await?;
+
+    let original_jar = context
+        .jar_contract
+        .get_jars_for_account(&alice)
+        .await?
+        .into_iter()
+        .next()
+        .unwrap();
+
+    let transferred_jar = context
+        .jar_contract
+        .transfer_jar(&alice, original_jar.id, bob.id())
+        .await?;
+
+    assert_ne!(original_jar.id, transferred_jar.id);
+    assert_eq!(original_jar.principal, transferred_jar.principal);
+    assert_eq!(bob.id().as_str(), transferred_jar.account_id.as_str());
+
+    assert!(context.jar_contract.get_jars_for_account(&alice).await?.is_empty());
+
+    let bob_principal = context.jar_contract.get_total_principal(&bob).await?;
+    assert_eq!(1_000_000, bob_principal.get_u128("total"));
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use near_sdk::{
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+};
+
+/// The `ExitView` struct represents the result of withdrawing principal and claiming interest
+/// from several deposit jars at once.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ExitView {
+    /// The amount of principal that has been transferred to the user's account, excluding fees.
+    pub withdrawn_amount: U128,
+
+    /// The total withdrawal fee charged by the products of the jars.
+    pub fee: U128,
+
+    /// The amount of interest that has been transferred to the user's account.
+    pub claimed_amount: U128,
+}
+
+impl ExitView {
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            withdrawn_amount: U128(0),
+            fee: U128(0),
+            claimed_amount: U128(0),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod exit;
 pub mod jar;
 mod numbers;
 pub mod withdraw;
@@ -10,3 +10,5 @@ cargo test --package integration-tests --lib measure::stake::measure_stake_total
 cargo test --package integration-tests --lib measure::restake::measure_restake_total_test -- --ignored --exact
 cargo test --package integration-tests --lib measure::claim::measure_claim_total_test -- --ignored --exact
 cargo test --package integration-tests --lib measure::withdraw::measure_withdraw_total_test -- --ignored --exact
+cargo test --package integration-tests --lib measure::top_up::measure_top_up_test -- --ignored --exact
+cargo test --package integration-tests --lib measure::restake_all::measure_restake_all_total_test -- --ignored --exact